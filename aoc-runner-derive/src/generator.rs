@@ -1,59 +1,104 @@
-use crate::types::Generator;
-use crate::utils;
-use crate::AOC_RUNNER;
-use aoc_runner_internal::{DayPart, Part};
+use crate::utils::{self, generator_key, AocArgs};
+use aoc_runner_internal::Part;
 use proc_macro as pm;
+use proc_macro2 as pm2;
+use quote::quote;
+use syn::visit_mut::{self, VisitMut};
 use syn::*;
 
 pub fn generator_impl(args: pm::TokenStream, input: pm::TokenStream) -> pm::TokenStream {
-    let (day, part, name) = utils::extract_meta(args);
-    let day = day
-        .to_string()
-        .parse()
-        .expect("generators must have defined day");
-    let part = part.and_then(|p| p.to_string().parse().ok());
-    let name = name.map(|i| i.to_string());
-
     let input_cloned = input.clone();
 
+    let AocArgs {
+        day,
+        part,
+        name,
+        params,
+        ..
+    } = match syn::parse(args) {
+        Ok(args) => args,
+        Err(e) => return utils::compile_error(e, input_cloned),
+    };
+    if let Some(params) = params {
+        let error = Error::new(
+            params.span,
+            "generators don't take parameters, solutions do",
+        );
+        return utils::compile_error(error, input_cloned);
+    }
+    let name = name.map(|i| i.to_string());
+
     let input = parse_macro_input!(input as ItemFn);
 
+    let original_fn = input.clone();
+
+    let cfgs = utils::cfg_attrs(&input.attrs);
+
     let fn_name = input.sig.ident;
-    let out_t = if let ReturnType::Type(_, p) = input.sig.output {
+    let mut out_t = if let ReturnType::Type(_, p) = input.sig.output {
         p
     } else {
-        panic!("cannot find output type for {}", fn_name)
+        let error = Error::new(fn_name.span(), "generators must return the generated input");
+        return utils::compile_error(error, input_cloned);
     };
 
-    let (special_type, out_t) = if let Some((ty, inner)) = utils::extract_result(&out_t) {
-        (Some(ty), Box::new(inner))
-    } else {
-        (None, out_t)
-    };
+    InputLifetime {
+        generics: input
+            .sig
+            .generics
+            .lifetimes()
+            .map(|param| param.lifetime.ident.clone())
+            .collect(),
+    }
+    .visit_type_mut(&mut out_t);
 
-    AOC_RUNNER.with(|map| {
-        let mut map = map
-            .borrow_mut()
-            .expect("failed to borrow shared map from generator");
-
-        let mut register = |p: Part| {
-            let runner = map
-                .entry(DayPart {
-                    day,
-                    part: p,
-                    name: name.clone(),
-                })
-                .or_default();
-            runner.with_generator(Generator::new(&fn_name, &out_t, special_type));
+    // Solutions pick their generator by this key, see `aoc_runner::Select`
+    let key = generator_key(day, part.unwrap_or(Part(0)), name.as_deref());
+
+    pm::TokenStream::from(quote! {
+        #original_fn
+
+        #(#cfgs)*
+        const _: () = {
+            // `Result`s and `Option`s are unwrapped by the solutions, see `aoc_runner::Fallible`.
+            // Paths are qualified, as the output type may use the crate's own `Error` or `Result`
+            impl<'input> aoc_runner::Generator<'input, #out_t> for crate::#key {
+                fn generate(
+                    input: &'input aoc_runner::ArcStr,
+                ) -> std::result::Result<#out_t, std::boxed::Box<dyn std::error::Error>> {
+                    std::result::Result::Ok( #fn_name(std::borrow::Borrow::borrow(input)) )
+                }
+            }
         };
+    })
+}
+
+/// Names `'input` the lifetimes of an output borrowing from the input: the elided ones, `'_` and
+/// those of the generator, like in `fn parse<'a>(input: &'a str) -> Vec<&'a str>`
+struct InputLifetime {
+    generics: Vec<Ident>,
+}
+
+impl InputLifetime {
+    fn lifetime(span: pm2::Span) -> Lifetime {
+        Lifetime::new("'input", span)
+    }
+}
+
+impl VisitMut for InputLifetime {
+    fn visit_type_reference_mut(&mut self, reference: &mut TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(Self::lifetime(reference.and_token.span));
+        }
+        visit_mut::visit_type_reference_mut(self, reference);
+    }
 
-        if let Some(p) = part {
-            register(p);
-        } else {
-            register(Part(1));
-            register(Part(2));
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" || self.generics.contains(&lifetime.ident) {
+            *lifetime = Self::lifetime(lifetime.span());
         }
-    });
+    }
 
-    input_cloned
+    // Elided lifetimes of function pointers are their own
+    fn visit_type_bare_fn_mut(&mut self, _: &mut TypeBareFn) {}
 }
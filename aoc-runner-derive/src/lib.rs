@@ -8,22 +8,16 @@ extern crate quote;
 extern crate syn;
 
 mod generator;
-mod map;
 mod out;
 mod runner;
 mod types;
 mod utils;
 
-use crate::map::Map;
 use crate::utils::is_rls;
 use proc_macro as pm;
 use proc_macro2 as pm2;
 use quote::quote;
 
-thread_local! {
-    static AOC_RUNNER: Map = Map::new();
-}
-
 #[proc_macro_attribute]
 /// # Solution meta
 ///
@@ -36,14 +30,30 @@ thread_local! {
 /// The function must take a single parameter : a `&str` or a `&[u8]`, unless you use a [generator]
 /// and return any type implementing `Display`.
 ///
+/// The input can also be taken as `&mut T` or by value, as `T` (`String` or `Vec<u8>` without generator):
+/// the solution then gets a clone of the generator output on every run.
+///
+/// ## Both parts
+///
+/// A function computing the answers of both parts at once is flagged `#[aoc(day1, both)]`, and
+/// returns them as a tuple `(part1, part2)` (or a `Result`/`Option` of it). It's run once, and both
+/// answers are reported with a single timing.
+///
+/// ## Parameters
+///
+/// Constants that differ between the examples and the real input are declared with their default
+/// values, like `#[aoc(day18, part1, params(size = 71, steps = 1024))]`, and taken as second
+/// argument: `params: &aoc_runner::Params`, read with `params.get::<usize>("size")`.
+/// They're overridden by `cargo aoc --param size=7`, or by the `[params]` of an example.
+///
 /// ## Results & Options
 ///
 /// Since 0.2.0, you can output `Result` & `Option` from solution function, with the following constraints :
-///  - the output type must be named `Result` or `Option`, `type CustomResult<T> = Result<T, CustomError>;` cannot be used in return position.
-///  - the first generic parameter must implement `Display`
-///  - for `Result`s, the error must implement `Into<std::error::Error>`
+///  - the `Ok` / `Some` type must implement `Display`
+///  - for `Result`s, the error must implement `Into<Box<dyn std::error::Error>>`
 ///
-/// You still can use a path before the `Result`/`Option`, like this : `std::io::Result<i32>`
+/// Any name can be used, like `std::io::Result<i32>`, `anyhow::Result<i32>` or your own
+/// `type CustomResult<T> = Result<T, CustomError>;`.
 ///
 /// [generator]: attr.aoc_generator.html
 pub fn aoc(args: pm::TokenStream, input: pm::TokenStream) -> pm::TokenStream {
@@ -71,18 +81,22 @@ pub fn aoc(args: pm::TokenStream, input: pm::TokenStream) -> pm::TokenStream {
 ///
 /// The function must take a single parameter : a `&str` or a `&[u8]`, and output any sized type.
 ///
-/// The corresponding solutions now take any parameter for which `Borrow` is implemented.
+/// The output can borrow from the input, like `Vec<&str>`: the input is kept alive as long as the
+/// output. Types with a hidden lifetime must then show it, like `Parsed<'_>`.
+///
+/// The corresponding solutions now take any parameter for which `Borrow` is implemented,
+/// or `BorrowMut` / `Into` for solutions taking a `&mut T` or a `T`.
 ///
 /// ## Results & Options
 ///
-/// Since 0.2.0, you can output `Result` & `Option` from generator function, with the following constraints :
-///  - the output type must be named `Result` or `Option`, `type CustomResult<T> = Result<T, CustomError>;` cannot be used in return position.
-///  - for `Result`s, the error must implement `Into<std::error::Error>`
+/// Since 0.2.0, you can output `Result` & `Option` from generator function, with the following constraint :
+///  - for `Result`s, the error must implement `Into<Box<dyn std::error::Error>>`
 ///
-/// You still can use a path before the `Result`/`Option`, like this : `std::io::Result<i32>`
+/// Any name can be used, like `std::io::Result<i32>`, `anyhow::Result<i32>` or your own
+/// `type CustomResult<T> = Result<T, CustomError>;`.
 ///
 /// ## Note
-/// A generator must be declared before it's solutions.
+/// A generator can be declared before or after its solutions, anywhere in the crate.
 ///
 pub fn aoc_generator(args: pm::TokenStream, input: pm::TokenStream) -> pm::TokenStream {
     if is_rls() {
@@ -99,7 +113,9 @@ pub fn aoc_generator(args: pm::TokenStream, input: pm::TokenStream) -> pm::Token
 #[proc_macro]
 /// # Library declaration
 ///
-/// This macro must be at the end of lib.rs
+/// Solutions register themselves when the crate is linked, so this macro can be anywhere in lib.rs,
+/// although it's usually at the end.
+/// They are listed by `Factory::solutions()`.
 ///
 /// ## Usage
 /// `aoc_lib! { year = 2018 }`
@@ -120,6 +136,8 @@ pub fn aoc_lib(input: pm::TokenStream) -> pm::TokenStream {
 /// `aoc_main` has 2 forms :
 ///  - as a standalone binary : `aoc_main! { year = 2018 }`
 ///  - as a link to a library : `aoc_main! { lib = advent_of_code_2018 }` (you must had `extern crate advent_of_code_2018;` before)
+///
+/// Inputs are read when running, from `input/{year}/day{day}.txt` in the crate's directory.
 pub fn aoc_main(input: pm::TokenStream) -> pm::TokenStream {
     if is_rls() {
         return pm::TokenStream::from(quote! { fn main() {} });
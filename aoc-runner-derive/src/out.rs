@@ -1,11 +1,6 @@
-use crate::map::InnerMap;
-use crate::utils::{to_camelcase, to_input, to_snakecase};
-use crate::AOC_RUNNER;
-use aoc_runner_internal::{DayParts, DayPartsBuilder};
 use proc_macro as pm;
 use proc_macro2 as pm2;
 use quote::quote;
-use std::error;
 
 #[derive(Debug)]
 struct LibInfos {
@@ -21,202 +16,130 @@ enum MainInfos {
 pub fn lib_impl(input: pm::TokenStream) -> pm::TokenStream {
     let infos = parse_lib_infos(input).expect("failed to parse lib infos");
 
-    AOC_RUNNER.with(|map| {
-        let map = map.consume().expect("failed to consume map from lib");
-
-        let year = infos.year;
-
-        write_infos(&map, year).expect("failed to write infos from lib");
-
-        pm::TokenStream::from(headers(&map, year))
-    })
+    pm::TokenStream::from(headers(infos.year))
 }
 
 pub fn main_impl(input: pm::TokenStream) -> pm::TokenStream {
     let infos = parse_main_infos(input).expect("failed to parse main infos");
 
-    AOC_RUNNER.with(|map| {
-        let map = map.consume().expect("failed to consume map from main");
+    let expanded = match infos {
+        MainInfos::Ref { lib } => body(Some(lib)),
+        MainInfos::Standalone { year } => {
+            let headers = headers(year);
+            let body = body(None);
 
-        let expanded = match infos {
-            MainInfos::Ref { lib } => {
-                let infos = read_infos().expect("failed to read infos from ref main");
-                body(&infos, Some(lib))
-            }
-            MainInfos::Standalone { year } => {
-                let infos =
-                    write_infos(&map, year).expect("failed to write infos from standalone main");
-                let headers = headers(&map, year);
-                let body = body(&infos, None);
-
-                quote! {
-                    #headers
+            quote! {
+                #headers
 
-                    #body
-                }
+                #body
             }
-        };
+        }
+    };
 
-        pm::TokenStream::from(expanded)
-    })
+    pm::TokenStream::from(expanded)
 }
 
-fn headers(map: &InnerMap, year: u32) -> pm2::TokenStream {
-    let traits_impl: pm2::TokenStream = map
-        .keys()
-        .map(|dp| {
-            let snake = to_snakecase(dp);
-            let camel = to_camelcase(dp);
-
-            quote! {
-                #[doc(hidden)]
-                pub trait #camel {
-                    fn #snake(input: ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
-                }
-            }
-        })
-        .collect();
-
+fn headers(year: u32) -> pm2::TokenStream {
     quote! {
         pub use self::aoc_factory::*;
 
         #[allow(unused)]
         mod aoc_factory {
-            use aoc_runner::{Runner, ArcStr};
-            use std::error::Error;
+            use aoc_runner::Solution;
 
             #[doc(hidden)]
             pub static YEAR : u32 = #year;
 
+            /// Solutions are added to the factory by `#[aoc]`, e.g. `Factory::day1_part1`
             #[doc(hidden)]
             pub struct Factory();
 
-            #traits_impl
+            impl Factory {
+                /// Every solution of the crate, by day, part and name
+                pub fn solutions() -> Vec<&'static Solution> {
+                    aoc_runner::solutions()
+                }
+            }
+
+            /// Implements `aoc_runner::Generator` for the generators of a day, part and name
+            #[doc(hidden)]
+            pub struct GeneratorFor<const DAY: u8, const PART: u8, const NAME: u64>;
         }
     }
 }
 
-fn body(infos: &DayParts, lib: Option<pm2::Ident>) -> pm2::TokenStream {
-    let mut days: Vec<_> = infos.iter().map(|dp| dp.day).collect();
-    days.sort();
-    days.dedup();
-
-    let inputs: pm2::TokenStream = days
-        .into_iter()
-        .map(|d| {
-            let name = to_input(d);
-            let input = format!("../input/{}/day{}.txt", infos.year, d.0);
-
-            quote! { let #name = ArcStr::from(include_str!(#input)); }
-        })
-        .collect();
-
-    let body : pm2::TokenStream = infos.iter().map(|dp| {
-        let identifier = to_snakecase(dp);
-        let (pattern, err) = if let Some(n) = &dp.name {
-            (
-                format!(
-                    "Day {} - Part {} - {}: {{}}\n\tgenerator: {{:?}},\n\trunner: {{:?}}\n",
-                    dp.day.0, dp.part.0, n
-                ),
-                format! (
-                    "Day {} - Part {} - {}: FAILED while {{}}:\n{{:#?}}\n",
-                    dp.day.0, dp.part.0, n
-                )
-            )
-        } else {
-            (
-                format!(
-                    "Day {} - Part {}: {{}}\n\tgenerator: {{:?}},\n\trunner: {{:?}}\n",
-                    dp.day.0, dp.part.0
-                ),
-                format! (
-                    "Day {} - Part {}: FAILED while {{}}:\n{{:#?}}\n",
-                    dp.day.0, dp.part.0
-                )
-            )
-        };
-
-        let input = to_input(dp.day);
+fn body(lib: Option<pm2::Ident>) -> pm2::TokenStream {
+    // Inputs are read when running, from the `input` directory of the crate
+    let main = quote! {
+        fn main() {
+            use aoc_runner::{ArcStr, Solution};
+            use std::collections::HashMap;
+            use std::fs;
+            use std::path::Path;
+            use std::time::Instant;
+
+            println!("Advent of code {}", YEAR);
+
+            let input_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("input")
+                .join(YEAR.to_string());
+            let mut inputs = HashMap::new();
+
+            for solution in Factory::solutions() {
+                let input = inputs.entry(solution.day).or_insert_with(|| {
+                    let path = input_dir.join(format!("day{}.txt", solution.day));
+                    fs::read_to_string(&path)
+                        .map(|input| ArcStr::from(&input))
+                        .map_err(|e| format!("{}: {}", path.display(), e))
+                });
+                let input = match input {
+                    Ok(input) => input.clone(),
+                    Err(e) => {
+                        eprintln!("{}: FAILED while reading input:\n{:#?}\n", solution, e);
+                        continue;
+                    }
+                };
 
-        quote! {
-            {
                 let start_time = Instant::now();
 
-                match Factory::#identifier(#input.clone()) {
+                match (solution.runner)(input) {
                     Ok(runner) => {
                         let inter_time = Instant::now();
 
-                        match runner.try_run() {
-                            Ok(result) => {
+                        // Solutions of both parts give both answers at once
+                        let results = match solution.part {
+                            0 => runner.try_run_both().map(|(part1, part2)| vec![(1, part1), (2, part2)]),
+                            _ => runner.try_run().map(|result| vec![(solution.part, result)]),
+                        };
+
+                        match results {
+                            Ok(results) => {
                                 let final_time = Instant::now();
-                                println!(#pattern, result, (inter_time - start_time), (final_time - inter_time));
+                                for (part, result) in results {
+                                    println!("{}: {:#}", Solution { part, ..*solution }, result);
+                                }
+                                println!("\tgenerator: {:?},\n\trunner: {:?}\n", (inter_time - start_time), (final_time - inter_time));
                             },
-                            Err(e) => eprintln!(#err, "running", e)
+                            Err(e) => eprintln!("{}: FAILED while running:\n{:#?}\n", solution, e)
                         }
                     },
-                    Err(e) => eprintln!(#err, "generating", e)
+                    Err(e) => eprintln!("{}: FAILED while generating:\n{:#?}\n", solution, e)
                 }
             }
         }
-    }).collect();
+    };
 
     if let Some(lib) = lib {
         quote! {
             use #lib::*;
 
-            fn main() {
-                use aoc_runner::ArcStr;
-                use std::time::{Duration, Instant};
-
-                #inputs
-
-                println!("Advent of code {}", YEAR);
-
-                #body
-            }
+            #main
         }
     } else {
-        quote! {
-            fn main() {
-                use aoc_runner::ArcStr;
-                use std::time::{Duration, Instant};
-
-
-                #inputs
-
-                println!("Advent of code {}", YEAR);
-
-                #body
-            }
-        }
+        main
     }
 }
 
-fn write_infos(map: &InnerMap, year: u32) -> Result<DayParts, Box<dyn error::Error>> {
-    let mut day_parts = map
-        .iter()
-        .filter_map(|(dp, runner)| {
-            if runner.solver.is_some() {
-                Some(dp.clone())
-            } else {
-                None
-            }
-        })
-        .collect::<DayPartsBuilder>()
-        .with_year(year);
-
-    day_parts.sort();
-
-    day_parts.save()?;
-
-    Ok(day_parts)
-}
-
-fn read_infos() -> Result<DayParts, Box<dyn error::Error>> {
-    DayParts::load()
-}
-
 fn parse_lib_infos(infos: pm::TokenStream) -> Result<LibInfos, ()> {
     let tokens: Vec<_> = infos.into_iter().collect();
 
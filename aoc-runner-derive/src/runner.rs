@@ -1,23 +1,32 @@
-use crate::types::{Generator, Solver, SpecialType};
-use crate::utils::{self, extract_result, to_camelcase, to_snakecase};
-use crate::AOC_RUNNER;
-use aoc_runner_internal::DayPart;
+use crate::types::{InputType, Solver};
+use crate::utils::{self, generator_key, to_snakecase, AocArgs};
+use aoc_runner_internal::{DayPart, Part};
 use proc_macro as pm;
 use proc_macro2 as pm2;
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::*;
 
 pub fn runner_impl(args: pm::TokenStream, input: pm::TokenStream) -> pm::TokenStream {
-    let (day, part, name) = utils::extract_meta(args);
-    let day = day
-        .to_string()
-        .parse()
-        .expect("runners must have a defined day");
-    let part = part
-        .expect("runners must have a defined part")
-        .to_string()
-        .parse()
-        .expect("runners must have a defined part");
+    let input_cloned = input.clone();
+
+    let AocArgs {
+        day,
+        day_span,
+        part,
+        name,
+        params,
+    } = match syn::parse(args) {
+        Ok(args) => args,
+        Err(e) => return utils::compile_error(e, input_cloned),
+    };
+    let part = match part {
+        Some(part) => part,
+        None => {
+            let error = Error::new(day_span, "missing part, like `#[aoc(day1, part1)]`");
+            return utils::compile_error(error, input_cloned);
+        }
+    };
+    let name_span = name.as_ref().map(|i| i.span());
     let name = name.map(|i| i.to_string());
 
     let dp = DayPart { day, part, name };
@@ -26,171 +35,268 @@ pub fn runner_impl(args: pm::TokenStream, input: pm::TokenStream) -> pm::TokenSt
 
     let original_fn = input.clone();
 
-    let fn_name = input.sig.ident;
+    let cfgs = utils::cfg_attrs(&input.attrs);
+
+    let fn_name = input.sig.ident.clone();
+    let error_span = name_span.unwrap_or_else(|| fn_name.span());
     let out_t = if let ReturnType::Type(_, p) = input.sig.output {
         p
     } else {
-        panic!()
+        let error = Error::new(fn_name.span(), "solutions must return the answer");
+        return utils::compile_error(error, input_cloned);
     };
 
-    let (special_type, out_t) = if let Some((ty, inner)) = extract_result(&out_t) {
-        (Some(ty), Box::new(inner))
-    } else {
-        (None, out_t)
+    let input_type = match input.sig.inputs.first() {
+        Some(FnArg::Typed(arg)) => InputType::of(&arg.ty),
+        _ => {
+            let error = Error::new(fn_name.span(), "solutions must take the input as argument");
+            return utils::compile_error(error, input_cloned);
+        }
     };
 
-    let def = AOC_RUNNER.with(|map| {
-        let mut map = map
-            .borrow_mut()
-            .expect("failed to borrow shared map from runner");
-
-        let dp = dp.clone();
-        let def = dp.without_name();
-
-        if !map.contains_key(&dp) && map.contains_key(&def) {
-            let mut val = map[&def].clone();
-            val.solver = None;
-            map.insert(dp.clone(), val);
+    // Parameters are the second argument, when they're declared
+    match (&params, input.sig.inputs.iter().nth(1)) {
+        (Some(params), None) => {
+            let error = Error::new(
+                params.span,
+                "solutions with parameters take them as second argument, like `params: &Params`",
+            );
+            return utils::compile_error(error, input_cloned);
         }
+        (None, Some(arg)) => {
+            let error = Error::new_spanned(
+                arg,
+                "solutions only take the input, unless they declare `params(name = value)`",
+            );
+            return utils::compile_error(error, input_cloned);
+        }
+        _ => {}
+    }
+    if let Some(arg) = input.sig.inputs.iter().nth(2) {
+        let error = Error::new_spanned(arg, "solutions take the input and their parameters");
+        return utils::compile_error(error, input_cloned);
+    }
 
-        let runner = map.entry(dp).or_default();
+    let both = dp.part == Part(0);
 
-        runner.with_solver(Solver::new(&fn_name, &out_t, special_type));
+    // The generator of the solution's name, of its part or of its day, see `aoc_runner::Select`
+    let generator = {
+        let named = generator_key(dp.day, dp.part, dp.name.as_deref());
+        let part = generator_key(dp.day, dp.part, None);
+        let day = generator_key(dp.day, Part(0), None);
 
-        let derive = build_derive(runner.solver.as_ref().unwrap(), runner.generator.as_ref());
+        quote! { Select::<#named, #part, #day> }
+    };
 
-        if let Some(generator) = &runner.generator {
-            let gen_out_t = &generator.get_out_t();
+    let solver = Solver::new(&fn_name, &out_t, input_type, params.map(|p| p.values));
+    let param_names = solver
+        .params
+        .iter()
+        .flatten()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    let def = build_derive(&solver, &generator, both);
+
+    // Spanned on the solution, as a duplicate solution makes these names collide
+    let mod_name = Ident::new(&to_snakecase(&dp).to_string(), error_span);
+    let (day, part) = (dp.day.0, dp.part.0);
+    let name = match &dp.name {
+        Some(name) => quote! { Some(#name) },
+        None => quote! { None },
+    };
 
-            quote! {
-                pub struct RunnerStruct {
-                    input: #gen_out_t,
-                    output: PhantomData<#out_t>,
-                }
+    // Like `mod_name`, so that rustc reports the collision on the name of the solution
+    let factory = quote_spanned! { error_span=>
+        pub fn #mod_name(input: ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>> {
+            Ok(Box::new( RunnerStruct::try_gen(input)? ))
+        }
+    };
 
-                #derive
-            }
-        } else {
-            quote! {
-                pub struct RunnerStruct {
-                    input: ArcStr,
-                    output: PhantomData<#out_t>,
-                }
+    let body = quote! {
+        use super::*;
+        use aoc_runner::{Answers, ArcStr, Runner, Select, Solution};
+        use aoc_runner::{time_batched, BenchFn, Params, Prepared, RunFn};
+        use aoc_runner::{Fallible, FallibleOption, FallibleResult, FallibleValue};
+        use aoc_runner::{GenerateDay, GenerateInput, GenerateNamed, GeneratePart};
+        use std::error::Error;
+        use std::fmt::Display;
+        use std::borrow::{Borrow, BorrowMut};
+        use std::cell::Cell;
+        use std::rc::Rc;
+        use std::time::Duration;
+        use crate::{Factory, GeneratorFor};
+
+        impl Factory {
+            #factory
+        }
 
-                #derive
+        aoc_runner::inventory::submit! {
+            Solution {
+                day: #day,
+                part: #part,
+                name: #name,
+                params: &[ #(#param_names),* ],
+                runner: Factory::#mod_name,
             }
         }
-    });
 
-    let mod_name = to_snakecase(&dp);
-    let trait_name = to_camelcase(&dp);
+        #def
+    };
+
+    let module = quote_spanned! { error_span=> mod #mod_name { #body } };
 
     pm::TokenStream::from(quote! {
         #original_fn
 
+        #(#cfgs)*
         #[allow(unused_imports)]
-        mod #mod_name {
-            use super::*;
-            use aoc_runner::{ArcStr, Runner};
-            use std::marker::PhantomData;
-            use std::error::Error;
-            use std::fmt::Display;
-            use std::borrow::Borrow;
-            use crate::{Factory, #trait_name};
-
-            impl #trait_name for Factory {
-                fn #mod_name(input: ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>> {
-                    Ok(Box::new( RunnerStruct::try_gen(input)? ))
-                }
-            }
-
-            #def
-        }
+        #module
     })
 }
 
-fn build_derive(solver: &Solver, generator: Option<&Generator>) -> pm2::TokenStream {
+fn build_derive(solver: &Solver, generator: &pm2::TokenStream, both: bool) -> pm2::TokenStream {
     let fn_runner = solver.get_name();
 
-    // Build the output, possibly using quasi-quotation
-    let input = if let Some(generator) = generator {
-        let fn_generator = generator.get_name();
-        quote! {
-            input: #fn_generator(input.borrow())
-        }
-    } else {
-        quote! {
-            input
+    // The parameters are made when generating, with their overrides, see `aoc_runner::Params`
+    let (params, params_clone) = match &solver.params {
+        Some(params) => {
+            let (names, defaults): (Vec<_>, Vec<_>) = params.iter().cloned().unzip();
+            (
+                quote! { let params = Params::new(&[ #( (#names, #defaults) ),* ]); },
+                quote! { let params = params.clone(); },
+            )
         }
+        None => (quote! {}, quote! {}),
     };
 
-    let gen = if let Some(t) = generator.and_then(|g| g.special_type) {
-        let input = match t {
-            SpecialType::Result => quote! { #input? },
-            SpecialType::Option => quote! { #input.ok_or("generator produce no value")? },
+    // `Result`s and `Option`s are unwrapped, whatever their name, see `aoc_runner::Fallible`
+    let answer = |input: pm2::TokenStream| {
+        let args = match &solver.params {
+            Some(_) => quote! { #input, &params },
+            None => input,
         };
-
         quote! {
-            fn gen(input: ArcStr) -> Self {
-                Self::try_gen(input).expect("failed to generate input")
-            }
-
-            fn try_gen(input: ArcStr) -> Result<Self, Box<dyn Error>> {
-                Ok( RunnerStruct {
-                    #input,
-                    output: PhantomData,
-                } )
+            {
+                let answer = #fn_runner(#args);
+                (&Fallible::of(&answer)).into_result(answer)
             }
         }
+    };
+    let answer = answer(solver.input.argument());
+    let (run, bench) = (quote! { #answer? }, quote! { #answer.unwrap() });
+
+    // Spanned on the return type, which must be a tuple
+    let parts = quote_spanned! { solver.out_span=> (part1, part2) };
+
+    // The answers of the solution, and how they're reported as a `Runner`
+    let (answers_t, answers, bench_answers, try_run) = if both {
+        (
+            quote! { Answers },
+            quote! {
+                let #parts = #run;
+                Ok( (Box::new(part1), Box::new(part2)) )
+            },
+            quote! {
+                let #parts = #bench;
+                black_box(&part1);
+                black_box(&part2);
+            },
+            quote! {
+                fn try_run(&self) -> Result<Box<dyn Display>, Box<dyn Error>> {
+                    let (part1, part2) = self.0.run()?;
+                    Ok( Box::new(format!("{}\n{}", part1, part2)) )
+                }
+
+                fn try_run_both(&self) -> Result<Answers, Box<dyn Error>> {
+                    self.0.run()
+                }
+            },
+        )
     } else {
-        quote! {
-            fn gen(input: ArcStr) -> Self {
-                RunnerStruct {
-                    #input,
-                    output: PhantomData,
+        (
+            quote! { Box<dyn Display> },
+            quote! { Ok( Box::new( #run ) ) },
+            quote! { black_box( &#bench ) },
+            quote! {
+                fn try_run(&self) -> Result<Box<dyn Display>, Box<dyn Error>> {
+                    self.0.run()
                 }
-            }
-        }
+            },
+        )
     };
 
-    let run = if let Some(t) = solver.special_type {
-        let runner = match t {
-            SpecialType::Result => quote! { #fn_runner(self.input.borrow())? },
-            SpecialType::Option => {
-                quote! { #fn_runner(self.input.borrow()).ok_or("runner produce no value")? }
-            }
-        };
+    // Inputs are cloned before timing the runs, see `aoc_runner::time_batched`
+    let setup = solver.input.setup();
+    let binding = solver.input.binding();
+    // The input solutions taking `&mut T` changed is dropped after the run, as benchmarks do
+    let (spent, keep) = match solver.input {
+        InputType::Mut => (
+            quote! { let spent = Cell::new(None); },
+            quote! { spent.set(Some(batch)); },
+        ),
+        InputType::Ref | InputType::Owned => (quote! {}, quote! {}),
+    };
+    let routine = match solver.input {
+        InputType::Ref => quote! { |()| { #bench_answers } },
+        InputType::Mut => quote! { |mut batch| { { #bench_answers } batch } },
+        InputType::Owned => quote! { |batch| { #bench_answers } },
+    };
 
-        quote! {
-            fn run(&self) -> Box<dyn Display> {
-                self.try_run().expect("failed to run")
-            }
+    quote! {
+        pub struct RunnerStruct(Prepared<Result<#answers_t, Box<dyn Error>>>);
 
-            fn try_run(&self) -> Result<Box<dyn Display>, Box<dyn Error>> {
-                Ok( Box::new( #runner ) )
+        impl Runner for RunnerStruct {
+            fn gen(input: ArcStr) -> Self {
+                Self::try_gen(input).expect("failed to generate input")
             }
 
-            fn bench(&self, black_box: fn(&dyn Display)) {
-                black_box( &#fn_runner(self.input.borrow()).unwrap() )
+            fn try_gen(input: ArcStr) -> Result<Self, Box<dyn Error>> {
+                // The generator output may borrow from the input, see `aoc_runner::Prepared`
+                let prepared = Prepared::new(input, |input| {
+                    let input = (&&&#generator::new()).generate(input)?;
+                    let input = Rc::new( (&Fallible::of(&input)).into_result(input)? );
+                    #params
+
+                    let run: RunFn<'_, _> = {
+                        let input = input.clone();
+                        #params_clone
+                        // Made beforehand, and dropped afterwards, so that runs are timed like
+                        // benchmarks
+                        let premade = Cell::new(Some(#setup));
+                        #spent
+                        Box::new(move || -> Result<#answers_t, Box<dyn Error>> {
+                            let #binding = premade.take().unwrap_or_else(|| #setup);
+                            let answers = (|| -> Result<#answers_t, Box<dyn Error>> {
+                                #answers
+                            })();
+                            #keep
+                            answers
+                        })
+                    };
+                    let bench: BenchFn<'_> =
+                        Box::new(move |black_box: fn(&dyn Display), iters: u64| {
+                            time_batched(iters, || #setup, #routine)
+                        });
+
+                    Ok( (run, bench) )
+                })?;
+
+                Ok( RunnerStruct(prepared) )
             }
-        }
-    } else {
-        quote! {
+
             fn run(&self) -> Box<dyn Display> {
-                Box::new( #fn_runner(self.input.borrow()) )
+                self.try_run().expect("failed to run")
             }
 
+            #try_run
+
             fn bench(&self, black_box: fn(&dyn Display)) {
-                black_box( &#fn_runner(self.input.borrow()) )
+                self.0.bench(black_box, 1);
             }
-        }
-    };
 
-    quote! {
-        impl Runner for RunnerStruct {
-            #gen
-
-            #run
+            fn bench_iters(&self, black_box: fn(&dyn Display), iters: u64) -> Duration {
+                self.0.bench(black_box, iters)
+            }
         }
     }
 }
@@ -3,79 +3,77 @@ use proc_macro2 as pm2;
 use quote::quote;
 use syn;
 
-#[derive(Clone, Debug, Default)]
-pub(crate) struct Runner {
-    pub generator: Option<Generator>,
-    pub solver: Option<Solver>,
+/// How a solution takes its input
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum InputType {
+    /// `&T`, borrowed from the generator output
+    Ref,
+    /// `&mut T`, borrowed from a clone of the generator output
+    Mut,
+    /// `T`, converted from a clone of the generator output
+    Owned,
 }
 
-impl Runner {
-    pub fn with_generator(&mut self, generator: Generator) {
-        if self.solver.is_some() {
-            panic!("Generators must be defined before solutions: {:?}", self);
-        }
-        if self.generator.is_some() {
-            panic!("A generator is already defined: {:?}", self);
+impl InputType {
+    pub fn of(ty: &syn::Type) -> InputType {
+        match ty {
+            syn::Type::Reference(r) if r.mutability.is_some() => InputType::Mut,
+            syn::Type::Reference(_) => InputType::Ref,
+            _ => InputType::Owned,
         }
-        self.generator = Some(generator)
     }
 
-    pub fn with_solver(&mut self, solver: Solver) {
-        if self.solver.is_some() {
-            panic!("A solution is already defined: {:?}", self);
+    /// How the input of a run is made before timing it, see `aoc_runner::time_batched`: nothing
+    /// for `&T`, a clone of the generator output otherwise
+    pub fn setup(self) -> pm2::TokenStream {
+        match self {
+            InputType::Ref => quote! { () },
+            InputType::Mut => quote! { (*input).clone() },
+            InputType::Owned => quote! { (*input).clone().into() },
         }
-        self.solver = Some(solver)
     }
-}
-
-#[derive(Copy, Clone, Debug)]
-pub(crate) enum SpecialType {
-    Result,
-    Option,
-}
 
-#[derive(Clone, Debug)]
-pub(crate) struct Generator {
-    name: String,
-    out_t: String,
-    pub special_type: Option<SpecialType>,
-}
-
-impl Generator {
-    pub fn new(
-        name: &syn::Ident,
-        out_t: &syn::Type,
-        special_type: Option<SpecialType>,
-    ) -> Generator {
-        Generator {
-            name: name.to_string(),
-            out_t: quote! { #out_t }.to_string(),
-            special_type,
+    /// How a run binds what `setup` made
+    pub fn binding(self) -> pm2::TokenStream {
+        match self {
+            InputType::Ref => quote! { () },
+            InputType::Mut => quote! { mut batch },
+            InputType::Owned => quote! { batch },
         }
     }
 
-    pub fn get_name(&self) -> syn::Ident {
-        syn::Ident::new(&self.name, pm::Span::call_site().into())
-    }
-
-    pub fn get_out_t(&self) -> pm2::TokenStream {
-        self.out_t.parse().expect("failed to parse generator type")
+    /// The argument given to the solution: borrowed from `input`, an `Rc` of the generator
+    /// output, for `&T`, otherwise from `batch`, what `setup` made
+    pub fn argument(self) -> pm2::TokenStream {
+        match self {
+            InputType::Ref => quote! { (*input).borrow() },
+            InputType::Mut => quote! { BorrowMut::borrow_mut(&mut batch) },
+            InputType::Owned => quote! { batch },
+        }
     }
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct Solver {
     name: String,
-    //    out_t: String,
-    pub special_type: Option<SpecialType>,
+    pub out_span: pm2::Span,
+    pub input: InputType,
+    /// Names and default values of the parameters, given as second argument
+    pub params: Option<Vec<(String, String)>>,
 }
 
 impl Solver {
-    pub fn new(name: &syn::Ident, _out_t: &syn::Type, special_type: Option<SpecialType>) -> Solver {
+    pub fn new(
+        name: &syn::Ident,
+        out_t: &syn::Type,
+        input: InputType,
+        params: Option<Vec<(String, String)>>,
+    ) -> Solver {
         Solver {
             name: name.to_string(),
-            //            out_t: quote! { #out_t }.to_string(),
-            special_type,
+            out_span: syn::spanned::Spanned::span(out_t),
+            input,
+            params,
         }
     }
 
@@ -1,81 +1,201 @@
-use aoc_runner_internal::{Day, DayPart};
+use aoc_runner_internal::{Day, DayPart, Part};
 use proc_macro as pm;
+use proc_macro2 as pm2;
+use quote::{quote, quote_spanned};
 use syn;
-use types::SpecialType;
-
-pub(crate) fn extract_meta(
-    args: pm::TokenStream,
-) -> (syn::Ident, Option<syn::Ident>, Option<syn::Ident>) {
-    let mut idents = args.into_iter().filter_map(|a| {
-        if let pm::TokenTree::Ident(_) = a {
-            Some(a.into())
-        } else {
-            None
-        }
-    });
+use syn::parse::{Parse, ParseStream};
+
+/// Arguments of `#[aoc]` and `#[aoc_generator]`, like `day1, part1, Bytes, params(size = 71)`
+pub(crate) struct AocArgs {
+    pub day: Day,
+    pub day_span: pm2::Span,
+    pub part: Option<Part>,
+    pub name: Option<syn::Ident>,
+    pub params: Option<Params>,
+}
 
-    let day: pm::TokenStream = idents.next().expect("Couldn't find day");
-    let day: syn::Ident = syn::parse(day).expect("failed to parse day");
+/// Parameters of a solution, like `params(size = 71, steps = 1024)`
+pub(crate) struct Params {
+    pub span: pm2::Span,
+    /// Names and default values, as they're parsed when running
+    pub values: Vec<(String, String)>,
+}
 
-    let part = idents.next().and_then(|i| syn::parse(i).ok());
-    let name = idents.next().and_then(|i| syn::parse(i).ok());
+impl Parse for Params {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let keyword: syn::Ident = input.parse()?;
+        let content;
+        syn::parenthesized!(content in input);
+
+        let mut values: Vec<(String, String)> = Vec::new();
+        while !content.is_empty() {
+            let name: syn::Ident = content.parse()?;
+            content.parse::<syn::Token![=]>()?;
+            let value = match content.parse()? {
+                syn::Lit::Str(s) => s.value(),
+                syn::Lit::Char(c) => c.value().to_string(),
+                syn::Lit::Int(i) => i.base10_digits().to_string(),
+                syn::Lit::Float(f) => f.base10_digits().to_string(),
+                syn::Lit::Bool(b) => b.value.to_string(),
+                lit => return Err(syn::Error::new(lit.span(), "unsupported parameter value")),
+            };
+
+            if values.iter().any(|(n, _)| name == n) {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("duplicate parameter `{}`", name),
+                ));
+            }
+            values.push((name.to_string(), value));
 
-    (day, part, name)
+            if !content.is_empty() {
+                content.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        Ok(Params {
+            span: keyword.span(),
+            values,
+        })
+    }
 }
 
-pub(crate) fn extract_result(ty: &syn::Type) -> Option<(SpecialType, syn::Type)> {
-    use syn::*;
-
-    if let Type::Path(TypePath {
-        path: Path { segments: s, .. },
-        ..
-    }) = ty
-    {
-        if let Some(p) = s.last() {
-            if p.ident == "Result" {
-                if let PathArguments::AngleBracketed(a) = &p.arguments {
-                    if let Some(GenericArgument::Type(t)) = a.args.first() {
-                        return Some((SpecialType::Result, t.clone()));
-                    }
-                }
-            } else if p.ident == "Option" {
-                if let PathArguments::AngleBracketed(a) = &p.arguments {
-                    if let Some(GenericArgument::Type(t)) = a.args.first() {
-                        return Some((SpecialType::Option, t.clone()));
-                    }
+impl Parse for AocArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Err(input.error("expected a day, like `day1`"));
+        }
+
+        let day: syn::Ident = input.parse()?;
+        let day_span = day.span();
+        let day = day
+            .to_string()
+            .strip_prefix("day")
+            .and_then(|d| d.parse().ok())
+            .filter(|d| (1..=25).contains(d))
+            .map(Day)
+            .ok_or_else(|| {
+                syn::Error::new(
+                    day_span,
+                    format!("invalid day `{}`, expected `day1` to `day25`", day),
+                )
+            })?;
+
+        let mut part = None;
+        let mut name = None;
+        let mut params = None;
+
+        if next_argument(input)? && !is_params(input) {
+            let ident: syn::Ident = input.parse()?;
+            part = Some(match ident.to_string().as_str() {
+                "part1" => Part(1),
+                "part2" => Part(2),
+                // Like generators of the whole day
+                "both" => Part(0),
+                _ => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!(
+                            "invalid part `{}`, expected `part1`, `part2` or `both`",
+                            ident
+                        ),
+                    ))
                 }
+            });
+
+            if next_argument(input)? && !is_params(input) {
+                name = Some(input.parse()?);
+                next_argument(input)?;
             }
         }
+        if is_params(input) {
+            params = Some(input.parse()?);
+            next_argument(input)?;
+        }
+        if !input.is_empty() {
+            return Err(input
+                .error("unexpected argument, expected `dayN, partN, Name, params(name = value)`"));
+        }
+
+        Ok(AocArgs {
+            day,
+            day_span,
+            part,
+            name,
+            params,
+        })
     }
+}
 
-    None
+/// Whether the next argument is `params(...)`, rather than a name
+fn is_params(input: ParseStream) -> bool {
+    input.peek(syn::Ident) && input.peek2(syn::token::Paren) && {
+        let fork = input.fork();
+        fork.parse::<syn::Ident>()
+            .is_ok_and(|ident| ident == "params")
+    }
 }
 
-pub(crate) fn to_snakecase(dp: &DayPart) -> syn::Ident {
-    let DayPart { day, part, name } = dp;
-    let name = if let Some(name) = name {
-        format!("day{}_part{}_{}", day.0, part.0, name.to_lowercase())
-    } else {
-        format!("day{}_part{}", day.0, part.0)
-    };
+/// Parses the comma before the next argument, if there is one (a trailing comma is allowed)
+fn next_argument(input: ParseStream) -> syn::Result<bool> {
+    if input.is_empty() {
+        return Ok(false);
+    }
+    input.parse::<syn::Token![,]>()?;
+    Ok(!input.is_empty())
+}
 
-    syn::Ident::new(&name, pm::Span::call_site().into())
+/// The `#[cfg]` attributes of an item, for the code generated along with it to follow them
+pub(crate) fn cfg_attrs(attrs: &[syn::Attribute]) -> Vec<&syn::Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .collect()
 }
 
-pub(crate) fn to_camelcase(dp: &DayPart) -> syn::Ident {
-    let DayPart { day, part, name } = dp;
+/// Reports an error at compile time, keeping the annotated item so its uses don't fail too
+pub(crate) fn compile_error(error: syn::Error, input: pm::TokenStream) -> pm::TokenStream {
+    // Not `syn::Error::to_compile_error`: its `::core::compile_error!` doesn't resolve in crates of
+    // the 2015 edition
+    let errors = error.into_iter().map(|error| {
+        let message = error.to_string();
+        quote_spanned! { error.span()=> compile_error! { #message } }
+    });
+    let input: pm2::TokenStream = input.into();
+
+    pm::TokenStream::from(quote! {
+        #(#errors)*
+        #input
+    })
+}
 
+pub(crate) fn to_snakecase(dp: &DayPart) -> syn::Ident {
+    let DayPart { day, part, name } = dp;
+    let part = match part.0 {
+        0 => "both".to_string(),
+        part => format!("part{}", part),
+    };
     let name = if let Some(name) = name {
-        format!("Day{}Part{}{}", day.0, part.0, name.to_uppercase())
+        format!("day{}_{}_{}", day.0, part, name.to_lowercase())
     } else {
-        format!("Day{}Part{}", day.0, part.0)
+        format!("day{}_{}", day.0, part)
     };
 
     syn::Ident::new(&name, pm::Span::call_site().into())
 }
 
-pub(crate) fn to_input(d: Day) -> syn::Ident {
-    syn::Ident::new(&format!("input_day{}", d.0), pm::Span::call_site().into())
+/// The type implementing `aoc_runner::Generator` for a day, a part (`Part(0)` for the whole day)
+/// and a name, like `GeneratorFor<1, 0, 0>`
+pub(crate) fn generator_key(day: Day, part: Part, name: Option<&str>) -> pm2::TokenStream {
+    // FNV-1a, names can't be const generic parameters
+    let name = name.map_or(0, |name| {
+        name.bytes().fold(0xcbf29ce484222325, |hash: u64, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        })
+    });
+    let (day, part) = (day.0, part.0);
+
+    quote! { GeneratorFor<#day, #part, #name> }
 }
 
 pub(crate) fn is_rls() -> bool {
@@ -0,0 +1,20 @@
+extern crate aoc_runner;
+extern crate aoc_runner_derive;
+
+use aoc_runner_derive::{aoc, aoc_lib};
+
+#[aoc(day1, part1, Fast)]
+fn part1(input: &str) -> usize {
+    input.len()
+}
+
+#[aoc(day1, part1, Fast)]
+fn part1_again(input: &str) -> usize {
+    input.len()
+}
+
+aoc_lib! { year = 2018 }
+
+fn main() {
+    part1_again("");
+}
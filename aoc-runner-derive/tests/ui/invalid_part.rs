@@ -0,0 +1,15 @@
+extern crate aoc_runner;
+extern crate aoc_runner_derive;
+
+use aoc_runner_derive::{aoc, aoc_lib};
+
+#[aoc(day1, part3)]
+fn part3(input: &str) -> usize {
+    input.len()
+}
+
+aoc_lib! { year = 2018 }
+
+fn main() {
+    part3("");
+}
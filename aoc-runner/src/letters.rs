@@ -0,0 +1,273 @@
+use std::fmt::{self, Display};
+
+/// Letters of AoC's small font, 4 (or 5) pixels wide and 6 high, in cells of 5 columns
+const SMALL_FONT: (usize, &[(char, &str)]) = (
+    5,
+    &[
+        ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+        ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+        ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+        ('E', "####\n#...\n###.\n#...\n#...\n####"),
+        ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+        ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+        ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+        ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+        ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+        ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+        ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+        ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+        ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+        ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+        ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+        ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+    ],
+);
+
+/// Letters of AoC's large font, 6 pixels wide and 10 high, in cells of 8 columns
+#[rustfmt::skip]
+const LARGE_FONT: (usize, &[(char, &str)]) = (
+    8,
+    &[
+        ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+        ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+        ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+        ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+        ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+        ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+        ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+        ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+        ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+        ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+        ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+        ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+        ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+        ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+        ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+    ],
+);
+
+/// An answer drawn with pixels, like the letters on the screen of 2016 day 8 or 2022 day 10.
+///
+/// It displays as the letters it spells (`?` for the ones that can't be read), so that's what
+/// answers are compared with, and the alternate form (`{:#}`) also shows the drawing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LetterGrid {
+    rows: Vec<Vec<bool>>,
+}
+
+impl LetterGrid {
+    /// Rows of pixels, `true` when lit
+    pub fn new(rows: Vec<Vec<bool>>) -> LetterGrid {
+        let mut rows = rows;
+        // Blank rows around the letters would hide the size of the font
+        while rows.first().is_some_and(|row| !row.contains(&true)) {
+            rows.remove(0);
+        }
+        while rows.last().is_some_and(|row| !row.contains(&true)) {
+            rows.pop();
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(width, false);
+        }
+
+        LetterGrid { rows }
+    }
+
+    /// A drawing where `#` (or `█`) are lit pixels
+    pub fn from_art(art: &str) -> LetterGrid {
+        LetterGrid::new(
+            art.lines()
+                .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+                .collect(),
+        )
+    }
+
+    /// The coordinates `(x, y)` of the lit pixels, wherever they are
+    pub fn from_points<I: IntoIterator<Item = (i64, i64)>>(points: I) -> LetterGrid {
+        let points: Vec<_> = points.into_iter().collect();
+        if points.is_empty() {
+            return LetterGrid::new(Vec::new());
+        }
+
+        let (min_x, max_x, min_y, max_y) = points.iter().fold(
+            (i64::MAX, i64::MIN, i64::MAX, i64::MIN),
+            |(min_x, max_x, min_y, max_y), &(x, y)| {
+                (min_x.min(x), max_x.max(x), min_y.min(y), max_y.max(y))
+            },
+        );
+
+        let mut rows =
+            vec![vec![false; (max_x - min_x + 1) as usize]; (max_y - min_y + 1) as usize];
+        for (x, y) in points {
+            rows[(y - min_y) as usize][(x - min_x) as usize] = true;
+        }
+        LetterGrid::new(rows)
+    }
+
+    /// The letters of the drawing, read with the font of its height
+    pub fn letters(&self) -> String {
+        let (cell, glyphs) = match self.rows.len() {
+            6 => SMALL_FONT,
+            10 => LARGE_FONT,
+            _ => return "?".to_string(),
+        };
+
+        // The drawing may start with the blank columns of a letter like `J`
+        (0..cell)
+            .map(|offset| self.read(cell, glyphs, offset))
+            .min_by_key(|letters| letters.matches('?').count())
+            .unwrap_or_default()
+    }
+
+    /// Reads the letters in cells of `cell` columns, the first one starting `offset` columns early
+    fn read(&self, cell: usize, glyphs: &[(char, &str)], offset: usize) -> String {
+        let width = self.rows.first().map_or(0, Vec::len) + offset;
+
+        (0..width.div_ceil(cell))
+            .filter_map(|i| {
+                let columns = (i * cell)..((i + 1) * cell).min(width);
+                let mut lines: Vec<String> = self
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        columns
+                            .clone()
+                            .map(|x| match x.checked_sub(offset) {
+                                Some(x) if row[x] => '#',
+                                _ => '.',
+                            })
+                            .collect()
+                    })
+                    .collect();
+
+                // Letters are narrower than their cell
+                while lines.iter().all(|line| line.ends_with('.')) {
+                    for line in &mut lines {
+                        line.pop();
+                    }
+                }
+
+                if lines.iter().all(String::is_empty) {
+                    return None;
+                }
+
+                let glyph = lines.join("\n");
+                let letter = glyphs.iter().find(|(_, g)| *g == glyph);
+                Some(letter.map_or('?', |(letter, _)| *letter))
+            })
+            .collect()
+    }
+}
+
+impl Display for LetterGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.letters())?;
+
+        if f.alternate() {
+            for row in &self.rows {
+                writeln!(f)?;
+                for &pixel in row {
+                    write!(f, "{}", if pixel { '#' } else { '.' })?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_font() {
+        let ab = LetterGrid::from_art(
+            ".##..###.\n\
+             #..#.#..#\n\
+             #..#.###.\n\
+             ####.#..#\n\
+             #..#.#..#\n\
+             #..#.###.",
+        );
+        assert_eq!(ab.letters(), "AB");
+
+        let jz = LetterGrid::from_art(
+            "..##.####\n\
+             ...#....#\n\
+             ...#...#.\n\
+             ...#..#..\n\
+             #..#.#...\n\
+             .##..####",
+        );
+        assert_eq!(jz.letters(), "JZ");
+    }
+
+    #[test]
+    fn large_font() {
+        let hz = LetterGrid::from_art(
+            "#....#..######\n\
+             #....#.......#\n\
+             #....#.......#\n\
+             #....#......#.\n\
+             ######.....#..\n\
+             #....#....#...\n\
+             #....#...#....\n\
+             #....#..#.....\n\
+             #....#..#.....\n\
+             #....#..######",
+        );
+        assert_eq!(hz.letters(), "HZ");
+    }
+
+    #[test]
+    fn blank_borders() {
+        // Like a screen wider than the letters, with a blank row below them
+        let c = LetterGrid::from_art(
+            ".##.....\n\
+             #..#....\n\
+             #.......\n\
+             #.......\n\
+             #..#....\n\
+             .##.....\n\
+             ........",
+        );
+        assert_eq!(c.letters(), "C");
+    }
+
+    #[test]
+    fn leading_blank_column() {
+        // The first column of `I` is blank, so it's cut from the points
+        let i = [(1, 0), (2, 0), (3, 0), (2, 1), (2, 2), (2, 3), (2, 4)]
+            .iter()
+            .chain(&[(1, 5), (2, 5), (3, 5)])
+            .cloned();
+        assert_eq!(LetterGrid::from_points(i).letters(), "I");
+    }
+
+    #[test]
+    fn unknown_glyph() {
+        let a_box = LetterGrid::from_art(
+            ".##..####\n\
+             #..#.#..#\n\
+             #..#.#..#\n\
+             ####.#..#\n\
+             #..#.#..#\n\
+             #..#.####",
+        );
+        assert_eq!(a_box.letters(), "A?");
+
+        let too_short = LetterGrid::from_art("###\n#.#\n###");
+        assert_eq!(too_short.letters(), "?");
+    }
+
+    #[test]
+    fn display() {
+        let l = LetterGrid::from_art("#...\n#...\n#...\n#...\n#...\n####");
+        assert_eq!(l.to_string(), "L");
+        assert_eq!(format!("{:#}", l), "L\n#...\n#...\n#...\n#...\n#...\n####");
+    }
+}
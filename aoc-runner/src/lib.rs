@@ -1,7 +1,21 @@
+#[doc(hidden)]
+pub extern crate inventory;
+
+mod letters;
+mod params;
+
+pub use letters::LetterGrid;
+pub use params::Params;
+
 use std::borrow::Borrow;
+use std::cmp;
 use std::error::Error;
-use std::fmt::Display;
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+use std::mem;
+use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[inline]
 pub fn identity<T>(t: T) -> T {
@@ -36,6 +50,19 @@ impl Borrow<Arc<str>> for ArcStr {
     }
 }
 
+// For solutions taking their input by value, without generator
+impl From<ArcStr> for String {
+    fn from(input: ArcStr) -> String {
+        input.0.to_string()
+    }
+}
+
+impl From<ArcStr> for Vec<u8> {
+    fn from(input: ArcStr) -> Vec<u8> {
+        input.0.as_bytes().to_vec()
+    }
+}
+
 pub trait Runner {
     fn gen(input: ArcStr) -> Self
     where
@@ -55,4 +82,312 @@ pub trait Runner {
     fn try_run(&self) -> Result<Box<dyn Display>, Box<dyn Error>> {
         Ok(self.run())
     }
+
+    /// The answers of both parts, for the solutions of `#[aoc(dayN, both)]`
+    fn try_run_both(&self) -> Result<Answers, Box<dyn Error>> {
+        Err("the solution answers a single part".into())
+    }
+
+    /// The time `iters` runs take, leaving out the clones of the input made for solutions taking
+    /// it as `&mut T` or `T`
+    fn bench_iters(&self, black_box: fn(&dyn Display), iters: u64) -> Duration {
+        let start = Instant::now();
+        for _ in 0..iters {
+            self.bench(black_box);
+        }
+        start.elapsed()
+    }
+}
+
+/// The answers of both parts, `(part1, part2)`
+pub type Answers = (Box<dyn Display>, Box<dyn Display>);
+
+/// Generates the input of a solution, ready to run
+pub type RunnerFactory = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+/// A solution, registered by `#[aoc]`
+pub struct Solution {
+    pub day: u8,
+    /// `0` for a solution of both parts
+    pub part: u8,
+    pub name: Option<&'static str>,
+    /// The names of the parameters it declares, see `Params`
+    pub params: &'static [&'static str],
+    pub runner: RunnerFactory,
+}
+
+inventory::collect!(Solution);
+
+/// Every solution of the binary, by day, part and name
+pub fn solutions() -> Vec<&'static Solution> {
+    let mut solutions: Vec<_> = inventory::iter::<Solution>.into_iter().collect();
+    solutions.sort_by_key(|s| (s.day, s.part, s.name));
+    solutions
+}
+
+impl Solution {
+    /// The parts the solution answers
+    pub fn parts(&self) -> &'static [u8] {
+        match self.part {
+            0 => &[1, 2],
+            1 => &[1],
+            2 => &[2],
+            _ => &[],
+        }
+    }
+}
+
+impl Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.part {
+            0 => write!(f, "Day {} - Parts 1 & 2", self.day)?,
+            part => write!(f, "Day {} - Part {}", self.day, part)?,
+        }
+        if let Some(name) = self.name {
+            write!(f, " - {}", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Pre-processing of the input, implemented by `#[aoc_generator]`.
+///
+/// The output is a parameter rather than an associated type, so it can be private to the module
+/// of the generator. It may borrow from the input, for `'input`.
+pub trait Generator<'input, Output> {
+    fn generate(input: &'input ArcStr) -> Result<Output, Box<dyn Error>>;
+}
+
+/// Picks the most specific generator of a solution: the one of its name, of its part or of its day.
+/// Solutions without generator get the raw input.
+///
+/// The choice is made by method resolution (`(&&&Select::new()).generate(input)`), which tries
+/// `GenerateNamed` first, so generators can be declared anywhere.
+#[doc(hidden)]
+pub struct Select<Named, Part, Day>(PhantomData<(Named, Part, Day)>);
+
+impl<Named, Part, Day> Select<Named, Part, Day> {
+    #[inline]
+    pub fn new() -> Self {
+        Select(PhantomData)
+    }
+}
+
+// Not derived, as the parameters don't need to be `Copy`
+impl<Named, Part, Day> Clone for Select<Named, Part, Day> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Named, Part, Day> Copy for Select<Named, Part, Day> {}
+
+impl<Named, Part, Day> Default for Select<Named, Part, Day> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+pub trait GenerateNamed<'input, Output> {
+    fn generate(self, input: &'input ArcStr) -> Result<Output, Box<dyn Error>>;
+}
+
+impl<'input, Output, Named, Part, Day> GenerateNamed<'input, Output> for &&&Select<Named, Part, Day>
+where
+    Named: Generator<'input, Output>,
+{
+    #[inline]
+    fn generate(self, input: &'input ArcStr) -> Result<Output, Box<dyn Error>> {
+        Named::generate(input)
+    }
+}
+
+#[doc(hidden)]
+pub trait GeneratePart<'input, Output> {
+    fn generate(self, input: &'input ArcStr) -> Result<Output, Box<dyn Error>>;
+}
+
+impl<'input, Output, Named, Part, Day> GeneratePart<'input, Output> for &&Select<Named, Part, Day>
+where
+    Part: Generator<'input, Output>,
+{
+    #[inline]
+    fn generate(self, input: &'input ArcStr) -> Result<Output, Box<dyn Error>> {
+        Part::generate(input)
+    }
+}
+
+#[doc(hidden)]
+pub trait GenerateDay<'input, Output> {
+    fn generate(self, input: &'input ArcStr) -> Result<Output, Box<dyn Error>>;
+}
+
+impl<'input, Output, Named, Part, Day> GenerateDay<'input, Output> for &Select<Named, Part, Day>
+where
+    Day: Generator<'input, Output>,
+{
+    #[inline]
+    fn generate(self, input: &'input ArcStr) -> Result<Output, Box<dyn Error>> {
+        Day::generate(input)
+    }
+}
+
+#[doc(hidden)]
+pub trait GenerateInput {
+    fn generate(self, input: &ArcStr) -> Result<ArcStr, Box<dyn Error>>;
+}
+
+impl<Named, Part, Day> GenerateInput for Select<Named, Part, Day> {
+    #[inline]
+    fn generate(self, input: &ArcStr) -> Result<ArcStr, Box<dyn Error>> {
+        Ok(input.clone())
+    }
+}
+
+/// Runs a solution on the output of its generator, see `Prepared`
+pub type RunFn<'input, R> = Box<dyn Fn() -> R + 'input>;
+
+/// Times runs of a solution on the output of its generator, see `Prepared` and `time_batched`
+pub type BenchFn<'input> = Box<dyn Fn(fn(&dyn Display), u64) -> Duration + 'input>;
+
+/// A solution ready to run: closures running it on the output of its generator, which may borrow
+/// from the input, kept alive along with them.
+#[doc(hidden)]
+pub struct Prepared<R> {
+    // Declared before the input, so they're dropped first
+    run: RunFn<'static, R>,
+    bench: BenchFn<'static>,
+    _input: Rc<ArcStr>,
+}
+
+impl<R> Prepared<R> {
+    pub fn new<F>(input: ArcStr, prepare: F) -> Result<Self, Box<dyn Error>>
+    where
+        F: for<'input> FnOnce(
+            &'input ArcStr,
+        )
+            -> Result<(RunFn<'input, R>, BenchFn<'input>), Box<dyn Error>>,
+    {
+        let input = Rc::new(input);
+        let (run, bench) = prepare(&input)?;
+
+        // SAFETY: the closures are checked for any `'input`, so nothing borrowed from the input
+        // can get out of them, and the input doesn't move (it's behind the `Rc`) nor is dropped
+        // before them.
+        let (run, bench) = unsafe {
+            (
+                mem::transmute::<RunFn<'_, R>, RunFn<'static, R>>(run),
+                mem::transmute::<BenchFn<'_>, BenchFn<'static>>(bench),
+            )
+        };
+
+        Ok(Prepared {
+            run,
+            bench,
+            _input: input,
+        })
+    }
+
+    #[inline]
+    pub fn run(&self) -> R {
+        (self.run)()
+    }
+
+    #[inline]
+    pub fn bench(&self, black_box: fn(&dyn Display), iters: u64) -> Duration {
+        (self.bench)(black_box, iters)
+    }
+}
+
+/// Times `iters` calls of `routine`, on inputs made by `setup` beforehand, like criterion's
+/// `iter_batched`: making the inputs and dropping what `routine` returns isn't timed.
+#[doc(hidden)]
+pub fn time_batched<I, O, S, R>(iters: u64, mut setup: S, mut routine: R) -> Duration
+where
+    S: FnMut() -> I,
+    R: FnMut(I) -> O,
+{
+    // Bounds the memory taken by the inputs
+    const BATCH: u64 = 1000;
+
+    let mut elapsed = Duration::new(0, 0);
+    let mut remaining = iters;
+    while remaining > 0 {
+        let batch = cmp::min(remaining, BATCH);
+        remaining -= batch;
+
+        let inputs: Vec<I> = (0..batch).map(|_| setup()).collect();
+        let mut outputs = Vec::with_capacity(inputs.len());
+
+        let start = Instant::now();
+        for input in inputs {
+            outputs.push(routine(input));
+        }
+        elapsed += start.elapsed();
+    }
+    elapsed
+}
+
+/// Unwraps what solutions and generators return: `Result`s and `Option`s, whatever their name
+/// (`io::Result`, `anyhow::Result`, aliases...), while other types are kept as they are.
+///
+/// The choice is made by method resolution (`(&Fallible::of(&value)).into_result(value)`), which
+/// tries `FallibleResult` and `FallibleOption` before `FallibleValue`.
+#[doc(hidden)]
+pub struct Fallible<T>(PhantomData<T>);
+
+impl<T> Fallible<T> {
+    #[inline]
+    pub fn of(_value: &T) -> Self {
+        Fallible(PhantomData)
+    }
+}
+
+// Not derived, as `T` doesn't need to be `Copy`
+impl<T> Clone for Fallible<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Fallible<T> {}
+
+#[doc(hidden)]
+pub trait FallibleResult<T, E> {
+    fn into_result(self, value: Result<T, E>) -> Result<T, Box<dyn Error>>;
+}
+
+impl<T, E> FallibleResult<T, E> for &Fallible<Result<T, E>>
+where
+    E: Into<Box<dyn Error>>,
+{
+    #[inline]
+    fn into_result(self, value: Result<T, E>) -> Result<T, Box<dyn Error>> {
+        value.map_err(Into::into)
+    }
+}
+
+#[doc(hidden)]
+pub trait FallibleOption<T> {
+    fn into_result(self, value: Option<T>) -> Result<T, Box<dyn Error>>;
+}
+
+impl<T> FallibleOption<T> for &Fallible<Option<T>> {
+    #[inline]
+    fn into_result(self, value: Option<T>) -> Result<T, Box<dyn Error>> {
+        value.ok_or_else(|| "no value produced".into())
+    }
+}
+
+#[doc(hidden)]
+pub trait FallibleValue<T> {
+    fn into_result(self, value: T) -> Result<T, Box<dyn Error>>;
+}
+
+impl<T> FallibleValue<T> for Fallible<T> {
+    #[inline]
+    fn into_result(self, value: T) -> Result<T, Box<dyn Error>> {
+        Ok(value)
+    }
 }
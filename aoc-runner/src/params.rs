@@ -0,0 +1,120 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// Values given by `cargo aoc --param` or by the metadata of an example, for every solution
+static OVERRIDES: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+/// The parameters of a solution, declared like `#[aoc(day18, part1, params(size = 71))]`.
+///
+/// Their values can be overridden when running, e.g. to use the constants of an example.
+#[derive(Clone, Debug)]
+pub struct Params {
+    values: Vec<(&'static str, String)>,
+}
+
+impl Params {
+    /// The parameters of a solution with their default values, unless they're overridden
+    #[doc(hidden)]
+    pub fn new(defaults: &[(&'static str, &str)]) -> Params {
+        let overrides = OVERRIDES.lock().unwrap_or_else(|e| e.into_inner());
+
+        let values = defaults
+            .iter()
+            .map(|&(name, default)| {
+                let value = overrides
+                    .iter()
+                    .rev()
+                    .find(|(n, _)| n == name)
+                    .map_or(default, |(_, value)| value);
+                (name, value.to_string())
+            })
+            .collect();
+
+        Params { values }
+    }
+
+    /// Overrides the parameters of the solutions prepared from now on, as `cargo aoc --param`
+    /// does. Parameters that a solution doesn't declare are ignored.
+    pub fn set_overrides<I, N, V>(overrides: I)
+    where
+        I: IntoIterator<Item = (N, V)>,
+        N: Into<String>,
+        V: Into<String>,
+    {
+        *OVERRIDES.lock().unwrap_or_else(|e| e.into_inner()) = overrides
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.into()))
+            .collect();
+    }
+
+    /// The value of a parameter.
+    ///
+    /// # Panics
+    /// If the parameter isn't declared, or its value can't be parsed.
+    pub fn get<T>(&self, name: &str) -> T
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.try_get(name).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// The value of a parameter, or an error if it isn't declared or its value can't be parsed
+    pub fn try_get<T>(&self, name: &str) -> Result<T, Box<dyn Error>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let (_, value) = self
+            .values
+            .iter()
+            .find(|(n, _)| *n == name)
+            .ok_or_else(|| format!("unknown parameter `{}`", name))?;
+
+        value.parse().map_err(|e| {
+            format!("invalid value `{}` for parameter `{}`: {}", value, name, e).into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The overrides are global, so they're only set by a single test
+    #[test]
+    fn overrides() {
+        Params::set_overrides(vec![("size", "7"), ("unused", "1"), ("size", "12")]);
+        let params = Params::new(&[("size", "71"), ("steps", "1024")]);
+        Params::set_overrides(Vec::<(String, String)>::new());
+
+        assert_eq!(params.get::<usize>("size"), 12);
+        assert_eq!(params.get::<usize>("steps"), 1024);
+        assert!(params.try_get::<usize>("unused").is_err());
+        assert_eq!(Params::new(&[("size", "71")]).get::<usize>("size"), 71);
+    }
+
+    #[test]
+    fn values() {
+        let params = Params::new(&[("name", "abc"), ("ratio", "0.5")]);
+
+        assert_eq!(params.get::<String>("name"), "abc");
+        assert_eq!(params.try_get::<f64>("ratio").unwrap(), 0.5);
+
+        let err = params.try_get::<u32>("size").unwrap_err();
+        assert_eq!(err.to_string(), "unknown parameter `size`");
+        let err = params.try_get::<u32>("ratio").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value `0.5` for parameter `ratio`: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown parameter `size`")]
+    fn get_unknown() {
+        Params::new(&[]).get::<u32>("size");
+    }
+}
@@ -1,12 +1,18 @@
 use crate::{
-    credentials::CredentialsManager, date, project::ProjectManager, Bench, Credentials, Input,
+    codegen::{codegen, update_lib_rs},
+    config::Config,
+    credentials::CredentialsManager,
+    date,
+    example::Example,
+    network,
+    project::{template, ProjectManager},
+    scaffold::scaffold,
+    store::InputStore,
+    Bench, Credentials, CredentialsAction, Init, Input, New,
 };
-use aoc_runner_internal::{Day, Part};
+use aoc_runner_internal::{Day, DayPart, DayParts, Part};
 use date::AOCDate;
-use reqwest::{
-    header::{HeaderMap, COOKIE, USER_AGENT},
-    StatusCode,
-};
+use reqwest::{header::COOKIE, StatusCode};
 use std::io::Write;
 use std::path::Path;
 use std::process;
@@ -15,176 +21,351 @@ use std::{
     error::Error,
     fs::{self, File},
 };
+use tokio::sync::Semaphore;
 
 use crate::Cli;
 
-const CARGO_AOC_USER_AGENT: &str = "github.com/gobanos/cargo-aoc by gregory.obanos@gmail.com";
+const MAX_CONCURRENT_DOWNLOADS: usize = 4;
 
-pub fn execute_credentials(args: &Credentials) {
-    let mut creds_manager = CredentialsManager::new();
+pub fn execute_credentials(args: &Credentials, account: Option<&str>) {
+    let mut creds_manager = CredentialsManager::new(account);
 
-    if let Some(new_session) = &args.set {
+    let new_session = match &args.action {
+        Some(CredentialsAction::Set { token }) => Some(token.clone()),
+        Some(CredentialsAction::Default) => {
+            match creds_manager.set_default() {
+                Ok(()) => println!("{} is now the default account", creds_manager.account()),
+                Err(e) => println!("Error changing the default account: {}", e),
+            }
+            return;
+        }
+        Some(CredentialsAction::List) => {
+            let default = creds_manager.default_account();
+            for name in creds_manager.accounts() {
+                if name == default {
+                    println!("{} (default)", name);
+                } else {
+                    println!("{}", name);
+                }
+            }
+            return;
+        }
+        Some(CredentialsAction::Token(args)) => args.first().cloned(),
+        None => None,
+    };
+
+    let new_session = match args.from_browser {
+        Some(browser) => match browser.session_cookie() {
+            Ok(session) => Some(session),
+            Err(e) => {
+                println!("Error importing credentials: {}", e);
+                return;
+            }
+        },
+        None => new_session,
+    };
+
+    if args.unset {
+        match creds_manager.unset_session_token() {
+            Ok(()) => println!("Credentials of account {} removed", creds_manager.account()),
+            Err(e) => println!("Error removing credentials: {}", e),
+        }
+        return;
+    }
+
+    if let Some(new_session) = new_session {
         // Tries to set the session token
-        match creds_manager.set_session_token(new_session.to_owned()) {
+        match creds_manager.set_session_token(new_session) {
             Ok(()) => println!("Credentials sucessfully changed!"),
             Err(e) => println!("Error changing credentials: {}", e),
         }
     }
 
-    // Displays the stored session token
+    if args.check {
+        if let Err(e) = check_session(&creds_manager) {
+            println!("Error: {}", e);
+        }
+        return;
+    }
+
+    // Displays the stored session token, masked unless asked otherwise
     match creds_manager.get_session_token() {
-        Ok(cred) => println!("Current credentials: {}", cred),
+        Ok(cred) if args.show => println!("Current credentials: {}", cred),
+        Ok(cred) => println!("Current credentials: {}", mask_token(&cred)),
         Err(e) => println!("Error: {}", e),
     }
 }
 
+/// Hides all but both ends of a session token
+fn mask_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() < 16 {
+        return "*".repeat(chars.len());
+    }
+
+    let start: String = chars[..4].iter().collect();
+    let end: String = chars[chars.len() - 4..].iter().collect();
+    format!("{start}{}{end}", "*".repeat(chars.len() - 8))
+}
+
+/// Asks AoC who the session token belongs to
+fn check_session(creds: &CredentialsManager) -> Result<(), Box<dyn Error>> {
+    let token = creds.get_session_token()?;
+    // The project may set a contact and a timeout, but checking works anywhere
+    let config = ProjectManager::new()
+        .map(|pm| pm.config)
+        .unwrap_or_default();
+
+    let account = creds.account();
+    match network::session_user(&token, &network::base_url(), &config)? {
+        Some(user) => {
+            println!("The session token of account {account} is valid, logged in as {user}")
+        }
+        None => println!("The session token of account {account} is invalid or expired"),
+    }
+    if let Some(age) = creds.session_age() {
+        println!("It was set {age} days ago");
+    }
+    Ok(())
+}
+
 /// Executes the "input" subcommand of the app
-pub fn execute_input(args: &Input) -> Result<(), Box<dyn Error>> {
+pub fn execute_input(args: &Input, account: Option<&str>) -> Result<(), Box<dyn Error>> {
     // Gets the token or exit if it's not referenced.
-    let token = CredentialsManager::new().get_session_token().expect(
+    let creds = CredentialsManager::new(account);
+    let token = creds.get_session_token().expect(
         "Error: you need to setup your AOC token using \"cargo aoc credentials {token}\"",
     );
 
     let pm = ProjectManager::new()?;
 
-    let formated_token = format!("session={}", token);
-    let mut headers = HeaderMap::new();
-    headers.insert(USER_AGENT, CARGO_AOC_USER_AGENT.parse().unwrap());
-    headers.insert(COOKIE, formated_token.parse().unwrap());
-
+    let dates = AOCDate::select(args, pm.config.year)?;
     let generate = args.generate;
-    if args.all {
-        let year = args
-            .year
-            .expect("Need to specify a year to run cargo-aoc input --all");
+    // Materializing inputs only makes sense if they are kept in the store
+    let store = InputStore::new(
+        creds.account(),
+        &pm.config.input_dir,
+        args.store || args.export || args.link || pm.config.input_store,
+    );
+
+    let mut years: Vec<_> = dates.iter().map(|date| date.year).collect();
+    years.dedup();
+    if generate && years.len() > 1 {
+        Err("--generate can only be used with a single year")?
+    }
+
+    if args.all || dates.len() > 1 {
         {
+            let client = Arc::new(network::async_client(&pm.config)?);
+            let formated_token = Arc::new(format!("session={}", token));
+            let base_url = Arc::new(network::base_url());
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
-                let client = reqwest::Client::builder()
-                    .default_headers(headers)
-                    .build()
-                    .unwrap();
-                let client = Arc::new(client);
+                // Be gentle with the AoC servers when downloading several events at once
+                let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_DOWNLOADS));
                 let mut tasks = Vec::new();
-                for day in 1..26u32 {
+                for &date in &dates {
                     let client = client.clone();
-                    let pm = pm.clone();
+                    let formated_token = formated_token.clone();
+                    let base_url = base_url.clone();
+                    let permits = permits.clone();
+                    let filename = store.locate(&date);
+                    let force = args.force;
+                    let AOCDate { day, year } = date;
                     tasks.push(tokio::spawn(async move {
-                        let date = AOCDate { day, year };
-                        match download_input_async(date, &client).await {
-                            Ok(_) => println!("Successfully downloaded day {day}"),
+                        let permit = permits.acquire_owned().await;
+                        let request = client
+                            .get(date.request_url(&base_url))
+                            .header(COOKIE, formated_token.as_str());
+                        match download_input_async(date, &filename, force, request).await {
+                            Ok(true) => println!("Successfully downloaded {year} day {day}"),
+                            Ok(false) => {
+                                println!("Input for {year} day {day} already exists. Skipping...")
+                            }
                             Err(e) => eprintln!("{e}"),
                         };
-                        if generate {
-                            match codegen(day, &pm) {
-                                Ok(_) => {
-                                    println!("Successfully generated boilerplate for day {day}")
-                                }
-                                Err(e) => eprintln!("{e}"),
-                            }
-                        }
-                        day
+                        drop(permit);
                     }));
                 }
-                let mut results = Vec::new();
                 for task in tasks {
-                    let r = task.await;
-                    if let Ok(r) = r {
-                        results.push(r);
-                    }
-                }
-                results.sort_unstable();
-                for i in results {
-                    let _ =
-                        update_lib_rs(i, &pm).map_err(|e| eprintln!("Couldn't update lib.rs: {e}"));
+                    let _ = task.await;
                 }
             });
         };
+        if generate {
+            for &date in &dates {
+                match codegen(date, &pm, Some(&token)) {
+                    Ok(_) => println!("Successfully generated boilerplate for day {}", date.day),
+                    Err(e) => eprintln!("{e}"),
+                }
+                let _ =
+                    update_lib_rs(date, &pm).map_err(|e| eprintln!("Couldn't update lib.rs: {e}"));
+            }
+        }
+        if args.export || args.link {
+            for date in &dates {
+                if let Err(e) = export_input(date, &store, args.link) {
+                    eprintln!("{e}");
+                }
+            }
+        }
         return Ok(());
     }
 
-    // Creates the AOCDate struct from the arguments (defaults to today...)
-    let date: AOCDate = AOCDate::new(args);
-    download_input(date)?;
+    let Some(&date) = dates.first() else {
+        return Ok(());
+    };
+    download_input(date, &store.locate(&date), args.force, &creds, &pm.config)?;
+
+    if args.export || args.link {
+        export_input(&date, &store, args.link)?;
+    }
 
     if generate {
-        update_lib_rs(date.day, &pm)?;
-        codegen(date.day, &pm)?;
+        update_lib_rs(date, &pm)?;
+        codegen(date, &pm, Some(&token))?;
         println!("Successfully generated boilerplate for {}", date.day);
     }
     Ok(())
 }
 
-fn update_lib_rs(day: u32, pm: &ProjectManager) -> Result<(), Box<dyn Error>> {
-    let lib_rs_path = Path::new(pm.lib_path.as_deref().unwrap_or("src/lib.rs"));
-    if !lib_rs_path.exists() {
-        Err("lib.rs does not exist!")?
+/// Executes the "new" subcommand of the app
+pub fn execute_new(args: &New, account: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let status = process::Command::new("cargo")
+        .arg("new")
+        .arg("--lib")
+        .arg(&args.path)
+        .spawn()?
+        .wait()?;
+    if !status.success() {
+        return Err(format!("cargo new failed with code {}", status.code().unwrap_or(-1)).into());
+    }
+
+    std::env::set_current_dir(&args.path)?;
+    let year = args.year.unwrap_or_else(date::current_year);
+    scaffold(year, true, args.generate, account)?;
+    println!("Created aoc-runner project {}", args.path.display());
+    Ok(())
+}
+
+/// Executes the "init" subcommand of the app
+pub fn execute_init(args: &Init, account: Option<&str>) -> Result<(), Box<dyn Error>> {
+    if let Some(path) = &args.path {
+        fs::create_dir_all(path)?;
+        std::env::set_current_dir(path)?;
     }
 
-    let lib_rs = fs::read_to_string(lib_rs_path)?;
+    // Directories that are not crates yet are handed to cargo first
+    let fresh = !Path::new("Cargo.toml").exists();
+    if fresh {
+        let status = process::Command::new("cargo")
+            .args(["init", "--lib"])
+            .spawn()?
+            .wait()?;
+        if !status.success() {
+            return Err(format!(
+                "cargo init failed with code {}",
+                status.code().unwrap_or(-1)
+            )
+            .into());
+        }
+    }
+
+    let year = args.year.unwrap_or_else(date::current_year);
+    scaffold(year, fresh, args.generate, account)?;
+    println!("Initialized aoc-runner project");
+    Ok(())
+}
 
-    let str = format!("mod day{day};");
-    if !lib_rs.contains(&str) {
-        let lib_rs = format!("{str}\n{lib_rs}");
-        fs::write(lib_rs_path, lib_rs)?;
+/// Copies or links a stored input into the input directory
+fn export_input(date: &AOCDate, store: &InputStore, link: bool) -> Result<(), Box<dyn Error>> {
+    let AOCDate { day, year } = date;
+    let filename = store.local_filename(date);
+    if store.export(date, link)? {
+        println!("Exported {year} day {day} to {}", filename.display());
     } else {
-        eprintln!("lib.rs already contains {str}. Skipping...");
+        println!("{} already exists. Skipping...", filename.display());
     }
     Ok(())
 }
 
-fn codegen(day: u32, pm: &ProjectManager) -> Result<(), Box<dyn Error>> {
-    let src_dir = pm
-        .lib_path
-        .as_deref()
-        .map(Path::new)
-        .and_then(|lib_path| lib_path.parent())
-        .unwrap_or(Path::new("src"));
-    let filename = src_dir.join(format!("day{day}.rs"));
-    if filename.exists() {
-        eprintln!("{filename:?} already exists. Skipping...");
-        return Ok(());
+/// Detects bodies AoC answers with on HTTP 200 that are not puzzle inputs,
+/// like the log in prompt or the HTML error pages returned for an expired cookie.
+fn check_input(body: &str) -> Result<(), String> {
+    let start = body.trim_start().to_lowercase();
+
+    if body.contains("Puzzle inputs differ by user") {
+        Err("AoC asked to log in, is the session token correct or expired?".into())
+    } else if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        Err("AoC answered with an HTML page, is the session token correct or expired?".into())
+    } else if body.contains("before it unlocks") {
+        Err("this puzzle is not unlocked yet".into())
+    } else if start.is_empty() {
+        Err("AoC answered with an empty input".into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Whether an input should be (re-)downloaded.
+/// Existing inputs are kept unless forced, or unless they hold a saved error page.
+fn needs_download(filename: &Path, force: bool) -> bool {
+    if force || !filename.exists() {
+        return true;
+    }
+
+    match fs::read_to_string(filename)
+        .map_err(|e| e.to_string())
+        .and_then(|content| check_input(&content))
+    {
+        Ok(()) => false,
+        Err(e) => {
+            eprintln!(
+                "{} does not look like a puzzle input ({e}). Downloading it again...",
+                filename.display()
+            );
+            true
+        }
     }
-    let code = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/src/day.rs.tpl"
-    ))
-    .replace("{DAY}", &format!("day{day}"));
-    fs::write(filename, code)?;
-    Ok(())
 }
 
-// The client should have the appropriate headers set
+// The client should have the appropriate headers set.
+// Returns whether the input had to be downloaded.
 async fn download_input_async(
     date: AOCDate,
-    client: &reqwest::Client,
-) -> Result<(), Box<dyn error::Error>> {
-    let filename = date.filename();
-    let filename = Path::new(&filename);
-
-    if filename.exists() {
-        return Ok(());
+    filename: &Path,
+    force: bool,
+    request: reqwest::RequestBuilder,
+) -> Result<bool, Box<dyn error::Error>> {
+    if !needs_download(filename, force) {
+        return Ok(false);
     }
 
-    let response = client.get(&date.request_url()).send().await?;
+    let response = request.send().await?;
 
     match response.status() {
         StatusCode::OK => {
-            let dir = date.directory();
             // Creates the file-tree to store inputs
-            // TODO: Maybe use crate's infos to get its root in the filesystem ?
-            fs::create_dir_all(&dir)?;
+            if let Some(dir) = filename.parent() {
+                fs::create_dir_all(dir)?;
+            }
 
             // Gets the body from the response and outputs everything to a file
             let body = response
                 .text()
                 .await
                 .map_err(|e| format!("Can't convert response to text: {e:?}"))?;
+            check_input(&body).map_err(|e| {
+                format!(
+                    "Refusing to save the input of {} day {}: {e}",
+                    date.year, date.day
+                )
+            })?;
             let mut file = File::create(filename)?;
             file.write_all(body.as_bytes())?;
-            Ok(())
+            Ok(true)
         }
-        StatusCode::NOT_FOUND => Err(format!("Day {} not yet ready", date.day))?,
+        StatusCode::NOT_FOUND => Err(format!("{} day {} not yet ready", date.year, date.day))?,
         sc => Err(format!(
             "Could not find corresponding input. Is the token correct?\n\
                 Status: {}\n\n\
@@ -195,35 +376,38 @@ async fn download_input_async(
     }
 }
 
-fn download_input(date: AOCDate) -> Result<(), Box<dyn error::Error>> {
-    let filename = date.filename();
-    let filename = Path::new(&filename);
-
-    if filename.exists() {
+fn download_input(
+    date: AOCDate,
+    filename: &Path,
+    force: bool,
+    creds: &CredentialsManager,
+    config: &Config,
+) -> Result<(), Box<dyn error::Error>> {
+    if !needs_download(filename, force) {
         return Ok(());
     }
 
-    let token = CredentialsManager::new().get_session_token()?;
-    // Creates an HTTP Client
-    let client = reqwest::blocking::Client::new();
+    let token = creds.get_session_token()?;
     // Cookie formatting ...
     let formated_token = format!("session={}", token);
 
-    let response = client
-        .get(date.request_url())
-        .header(USER_AGENT, CARGO_AOC_USER_AGENT)
+    let response = network::client(config)?
+        .get(date.request_url(&network::base_url()))
         .header(COOKIE, formated_token)
         .send()?;
 
     match response.status() {
             StatusCode::OK => {
-                let dir = date.directory();
                 // Creates the file-tree to store inputs
-                // TODO: Maybe use crate's infos to get its root in the filesystem ?
-                fs::create_dir_all(dir)?;
+                if let Some(dir) = filename.parent() {
+                    fs::create_dir_all(dir)?;
+                }
 
                 // Gets the body from the response and outputs everything to a file
                 let body = response.text()?;
+                check_input(&body).map_err(|e| {
+                    format!("Refusing to save the input of {} day {}: {e}", date.year, date.day)
+                })?;
                 let mut file = File::create(filename)?;
                 file.write_all(body.as_bytes())?;
             }
@@ -237,9 +421,10 @@ fn download_input(date: AOCDate) -> Result<(), Box<dyn error::Error>> {
 }
 
 pub fn execute_default(args: &Cli) -> Result<(), Box<dyn error::Error>> {
+    let account = args.account.as_deref();
     let pm = ProjectManager::new()?;
 
-    let mut day_parts = pm.build_project()?;
+    let mut day_parts = build_runner(&pm, args.profile, &args.features)?;
 
     let part = args.part;
     let day = args
@@ -253,83 +438,75 @@ pub fn execute_default(args: &Cli) -> Result<(), Box<dyn error::Error>> {
     };
 
     if args.generate {
-        update_lib_rs(date.day, &pm)?;
-        codegen(date.day, &pm)?;
+        let session = CredentialsManager::new(account).get_session_token().ok();
+        update_lib_rs(date, &pm)?;
+        codegen(date, &pm, session.as_deref())?;
         println!("Successfully generated boilerplate for {}", date.day);
         // Rebuild to include newly generated day
-        day_parts = pm.build_project()?;
-    }
-
-    let cargo_content = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/Cargo-run.toml.tpl"
-    ))
-    .replace("{CRATE_NAME}", &pm.name)
-    .replace(
-        "{PROFILE}",
-        if args.profile {
-            "[profile.release]\ndebug = true"
-        } else {
-            ""
-        },
-    );
-
-    let template = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/src/runner.rs.tpl"
-    ));
-
-    let mut body = String::new();
-    for dp in day_parts.iter().filter(|dp| dp.day == day).filter(|dp| {
-        if let Some(p) = part {
-            dp.part == p
-        } else {
-            true
-        }
-    }) {
-        let (name, display) = if let Some(n) = &dp.name {
-            (
-                format!("day{}_part{}_{}", dp.day.0, dp.part.0, n.to_lowercase()),
-                format!("Day {} - Part {} - {}", dp.day.0, dp.part.0, n),
-            )
-        } else {
-            (
-                format!("day{}_part{}", dp.day.0, dp.part.0),
-                format!("Day {} - Part {}", dp.day.0, dp.part.0),
-            )
-        };
-
-        body += &template
-            .replace("{DAY}", &day.0.to_string())
-            .replace("{RUNNER_NAME}", &name)
-            .replace("{RUNNER_DISPLAY}", &display);
+        day_parts = build_runner(&pm, args.profile, &args.features)?;
     }
 
-    if body.is_empty() {
+    // Solutions of both parts (`Part(0)`) answer either part
+    if !day_parts
+        .iter()
+        .any(|dp| dp.day == day && part.is_none_or(|p| dp.part == p || dp.part == Part(0)))
+    {
         return Err("No matching day & part found".into());
     }
 
-    download_input(date)?;
-
-    let main_content = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/src/main.rs.tpl"
-    ))
-    .replace("{CRATE_SLUG}", &pm.slug)
-    .replace("{YEAR}", &day_parts.year.to_string())
-    .replace("{INPUT}", &template_input(day, year, args.input.as_deref()))
-    .replace("{BODY}", &body);
-
-    fs::create_dir_all("target/aoc/aoc-autobuild/src")
-        .expect("failed to create autobuild directory");
-    fs::write("target/aoc/aoc-autobuild/Cargo.toml", cargo_content)
-        .expect("failed to write Cargo.toml");
-    fs::write("target/aoc/aoc-autobuild/src/main.rs", main_content)
-        .expect("failed to write src/main.rs");
+    let example = args
+        .example
+        .map(|index| Example::load(&date, &pm.config.input_dir, index))
+        .transpose()?;
+
+    let input = match &example {
+        Some(example) => example.input.clone(),
+        None => {
+            let creds = CredentialsManager::new(account);
+            let store = InputStore::new(
+                creds.account(),
+                &pm.config.input_dir,
+                args.store || pm.config.input_store,
+            );
+            let input = store.locate(&date);
+            download_input(date, &input, false, &creds, &pm.config)?;
+            input
+        }
+    };
+    let input = args.input.as_deref().map(Path::new).unwrap_or(&input);
+
+    // The solutions are picked and the input is read by the runner itself
+    let mut run_args = vec![
+        "--day".to_string(),
+        day.0.to_string(),
+        "--input".to_string(),
+        fs::canonicalize(input)
+            .map_err(|e| format!("Failed to read {}: {e}", input.display()))?
+            .display()
+            .to_string(),
+    ];
+    if let Some(p) = part {
+        run_args.extend(["--part".to_string(), p.0.to_string()]);
+    }
+    if let (Some(index), Some(example)) = (args.example, &example) {
+        run_args.extend(["--label".to_string(), format!(" (example {index})")]);
+        for p in [Part(1), Part(2)] {
+            if let Some(answer) = example.answer(p) {
+                run_args.extend([format!("--expect{}", p.0), answer]);
+            }
+        }
+    }
+    // Those given on the command line win over the example's
+    for (name, value) in example_params(example.as_ref(), &args.params)? {
+        run_args.extend(["--param".to_string(), format!("{name}={value}")]);
+    }
 
     let status = process::Command::new("cargo")
         .args(["run", "--release"])
-        .current_dir("target/aoc/aoc-autobuild")
+        .args(pm.feature_args(&args.features))
+        .arg("--")
+        .args(&run_args)
+        .current_dir(RUNNER_DIR)
         .spawn()
         .expect("Failed to run cargo")
         .wait()
@@ -341,70 +518,79 @@ pub fn execute_default(args: &Cli) -> Result<(), Box<dyn error::Error>> {
     Ok(())
 }
 
-pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
+/// Where the crate running the solutions is generated
+const RUNNER_DIR: &str = "target/aoc/aoc-autobuild";
+
+/// Generates the crate running the solutions, builds it and lists the solutions of the project
+fn build_runner(
+    pm: &ProjectManager,
+    debug: bool,
+    features: &[String],
+) -> Result<DayParts, Box<dyn error::Error>> {
+    let cargo_content = template!(pm, "Cargo-run.toml.tpl")?
+        .replace("{CRATE_NAME}", &pm.name)
+        .replace("{PROFILE}", &pm.config.profile(debug));
+    let main_content = template!(pm, "src/main.rs.tpl")?.replace("{CRATE_SLUG}", &pm.slug);
+
+    let dir = Path::new(RUNNER_DIR);
+    fs::create_dir_all(dir.join("src")).expect("failed to create autobuild directory");
+    // Rewriting the same content would make cargo build the runner again
+    for (path, content) in [
+        (dir.join("Cargo.toml"), cargo_content),
+        (dir.join("src/main.rs"), main_content),
+    ] {
+        if fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+            fs::write(&path, content)?;
+        }
+    }
+
+    pm.build_project(dir, features)
+}
+
+pub fn execute_bench(args: &Bench, account: Option<&str>) -> Result<(), Box<dyn error::Error>> {
     let day: Option<Day> = args.day;
     let part: Option<Part> = args.part;
 
     let pm = ProjectManager::new()?;
 
-    let day_parts = pm.build_project()?;
+    let day_parts = build_runner(&pm, args.profile, &args.features)?;
 
     let day = day.unwrap_or_else(|| day_parts.last().expect("No implementation found").day);
     let year = day_parts.year;
 
-    let cargo_content = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/Cargo-bench.toml.tpl"
-    ))
-    .replace("{CRATE_NAME}", &pm.name)
-    .replace(
-        "{PROFILE}",
-        if args.profile {
-            "[profile.release]\ndebug = true"
-        } else {
-            ""
-        },
-    );
-
-    let bench_tpl = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/benches/aoc_benchmark.rs.tpl"
-    ));
-
-    let part_tpl = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/benches/part.rs.tpl"
-    ));
-
-    let gen_tpl = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/benches/gen.rs.tpl"
-    ));
-
-    let impl_tpl = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/benches/impl.rs.tpl"
-    ));
-
-    let gen_impl_tpl = include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/benches/gen_impl.rs.tpl"
-    ));
-
-    let matching_parts = day_parts.iter().filter(|dp| dp.day == day).filter(|dp| {
-        if let Some(p) = part {
-            dp.part == p
-        } else {
-            true
-        }
-    });
+    let generator = args.generator || pm.config.bench.generator;
+    let open = args.open || pm.config.bench.open;
+
+    let cargo_content = template!(pm, "Cargo-bench.toml.tpl")?
+        .replace("{CRATE_NAME}", &pm.name)
+        .replace("{PROFILE}", &pm.config.profile(args.profile));
+
+    let bench_tpl = template!(pm, "benches/aoc_benchmark.rs.tpl")?;
+    let part_tpl = template!(pm, "benches/part.rs.tpl")?;
+    let gen_tpl = template!(pm, "benches/gen.rs.tpl")?;
+    let impl_tpl = template!(pm, "benches/impl.rs.tpl")?;
+    let gen_impl_tpl = template!(pm, "benches/gen_impl.rs.tpl")?;
+
+    // Solutions of both parts (`Part(0)`) are benchmarked with each of the parts
+    let matching_parts: Vec<(Part, &DayPart)> = day_parts
+        .iter()
+        .filter(|dp| dp.day == day)
+        .flat_map(|dp| {
+            let parts = match dp.part {
+                Part(0) => vec![Part(1), Part(2)],
+                part => vec![part],
+            };
+            parts.into_iter().map(move |p| (p, dp))
+        })
+        .filter(|(p, _)| part.is_none_or(|part| *p == part))
+        .collect();
 
-    let mut parts: Vec<_> = matching_parts.clone().map(|dp| dp.part).collect();
+    let mut parts: Vec<_> = matching_parts.iter().map(|(p, _)| *p).collect();
     parts.sort();
     parts.dedup();
 
     let body: String = parts
-        .into_iter()
+        .iter()
         .map(|p| {
             let part_name = format!("day{}_part{}", day.0, p.0);
             part_tpl
@@ -414,32 +600,13 @@ pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
                 .replace(
                     "{IMPLS}",
                     &matching_parts
-                        .clone()
-                        .filter(|dp| dp.part == p)
-                        .map(|dp| {
+                        .iter()
+                        .filter(|(part, _)| part == p)
+                        .map(|(_, dp)| {
                             impl_tpl
-                                .replace(
-                                    "{RUNNER_NAME}",
-                                    &if let Some(n) = &dp.name {
-                                        format!(
-                                            "day{}_part{}_{}",
-                                            dp.day.0,
-                                            dp.part.0,
-                                            n.to_lowercase()
-                                        )
-                                    } else {
-                                        format!("day{}_part{}", dp.day.0, dp.part.0)
-                                    },
-                                )
+                                .replace("{RUNNER_NAME}", &runner_name(dp))
                                 .replace("{DAY}", &dp.day.0.to_string())
-                                .replace(
-                                    "{NAME}",
-                                    if let Some(n) = &dp.name {
-                                        n
-                                    } else {
-                                        "(default)"
-                                    },
-                                )
+                                .replace("{NAME}", &bench_name(dp))
                                 .replace("{PART_NAME}", &part_name)
                         })
                         .collect::<String>(),
@@ -451,13 +618,9 @@ pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
         return Err("No matching day & part found".into());
     }
 
-    let gens = if args.generator {
-        let mut parts: Vec<_> = matching_parts.clone().map(|dp| dp.part).collect();
-        parts.sort();
-        parts.dedup();
-
+    let gens = if generator {
         parts
-            .into_iter()
+            .iter()
             .map(|p| {
                 let gen_name = format!("day{}", day.0);
                 gen_tpl
@@ -467,32 +630,13 @@ pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
                     .replace(
                         "{IMPLS}",
                         &matching_parts
-                            .clone()
-                            .filter(|dp| dp.part == p)
-                            .map(|dp| {
+                            .iter()
+                            .filter(|(part, _)| part == p)
+                            .map(|(_, dp)| {
                                 gen_impl_tpl
-                                    .replace(
-                                        "{RUNNER_NAME}",
-                                        &if let Some(n) = &dp.name {
-                                            format!(
-                                                "day{}_part{}_{}",
-                                                dp.day.0,
-                                                dp.part.0,
-                                                n.to_lowercase()
-                                            )
-                                        } else {
-                                            format!("day{}_part{}", dp.day.0, dp.part.0)
-                                        },
-                                    )
+                                    .replace("{RUNNER_NAME}", &runner_name(dp))
                                     .replace("{DAY}", &dp.day.0.to_string())
-                                    .replace(
-                                        "{NAME}",
-                                        if let Some(n) = &dp.name {
-                                            n
-                                        } else {
-                                            "(default)"
-                                        },
-                                    )
+                                    .replace("{NAME}", &bench_name(dp))
                                     .replace("{GEN_NAME}", &gen_name)
                             })
                             .collect::<String>(),
@@ -507,15 +651,45 @@ pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
         day: u32::from(day.0),
         year: year as i32,
     };
-    download_input(date)?;
+    let example = args
+        .example
+        .map(|index| Example::load(&date, &pm.config.input_dir, index))
+        .transpose()?;
+    let params = example_params(example.as_ref(), &args.params)?;
+    let params = if params.is_empty() {
+        String::new()
+    } else {
+        let params = params
+            .iter()
+            .map(|(name, value)| format!("({name:?}, {value:?})"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("aoc_runner::Params::set_overrides(vec![{params}]);")
+    };
+
+    let input = match example {
+        Some(example) => example.input,
+        None => {
+            let creds = CredentialsManager::new(account);
+            let store = InputStore::new(
+                creds.account(),
+                &pm.config.input_dir,
+                args.store || pm.config.input_store,
+            );
+            let input = store.locate(&date);
+            download_input(date, &input, false, &creds, &pm.config)?;
+            input
+        }
+    };
 
     let main_content = bench_tpl
         .replace("{CRATE_SLUG}", &pm.slug)
         .replace("{PARTS}", &body)
         .replace("{GENS}", &gens)
+        .replace("{CONFIG}", &pm.config.criterion())
         .replace(
             "{BENCHMARKS}",
-            if args.generator {
+            if generator {
                 "aoc_benchmark, input_benchmark"
             } else {
                 "aoc_benchmark"
@@ -523,8 +697,9 @@ pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
         )
         .replace(
             "{INPUTS}",
-            &template_input(day, year, args.input.as_deref()),
-        );
+            &template_input(&pm, day, args.input.as_deref(), &input)?,
+        )
+        .replace("{PARAMS}", &params);
 
     fs::create_dir_all("target/aoc/aoc-autobench/benches")
         .expect("failed to create autobench directory");
@@ -538,6 +713,7 @@ pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
 
     let status = process::Command::new("cargo")
         .args(["bench"])
+        .args(pm.feature_args(&args.features))
         .current_dir("target/aoc/aoc-autobench")
         .spawn()
         .expect("Failed to run cargo")
@@ -548,7 +724,7 @@ pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
         process::exit(status.code().unwrap_or(-1));
     }
 
-    if args.open {
+    if open {
         let index = "target/aoc/aoc-autobench/target/criterion/report/index.html";
 
         if !Path::new(index).exists() {
@@ -560,21 +736,55 @@ pub fn execute_bench(args: &Bench) -> Result<(), Box<dyn error::Error>> {
     Ok(())
 }
 
-fn template_input(day: Day, year: u32, input: Option<&str>) -> String {
+/// The factory of a solution in the runner crate, like `day1_part1_fnv` or `day1_both`
+fn runner_name(dp: &DayPart) -> String {
+    let part = match dp.part {
+        Part(0) => "both".to_string(),
+        part => format!("part{}", part.0),
+    };
+    match &dp.name {
+        Some(name) => format!("day{}_{}_{}", dp.day.0, part, name.to_lowercase()),
+        None => format!("day{}_{}", dp.day.0, part),
+    }
+}
+
+/// The name of a solution in the benchmarks of a part, solutions of both parts being marked
+fn bench_name(dp: &DayPart) -> String {
+    match (&dp.name, dp.part) {
+        (Some(name), Part(0)) => format!("{name} (both)"),
+        (None, Part(0)) => "(both)".to_string(),
+        (Some(name), _) => name.clone(),
+        (None, _) => "(default)".to_string(),
+    }
+}
+
+/// The parameters of the solutions: those of the example, if any, then those given with `--param`
+pub(crate) fn example_params(
+    example: Option<&Example>,
+    params: &[(String, String)],
+) -> Result<Vec<(String, String)>, Box<dyn error::Error>> {
+    let mut all = match example {
+        Some(example) => example.params()?,
+        None => Vec::new(),
+    };
+    all.extend(params.iter().cloned());
+    Ok(all)
+}
+
+fn template_input(
+    pm: &ProjectManager,
+    day: Day,
+    input: Option<&str>,
+    default: &Path,
+) -> Result<String, Box<dyn error::Error>> {
     let day = day.0.to_string();
-    let path = input
-        .map(|p| {
-            if Path::new(p).is_relative() {
-                format!("../../../../{}", p)
-            } else {
-                p.to_string()
-            }
-        })
-        .unwrap_or_else(|| format!("../../../../input/{}/day{}.txt", year, &day));
-    include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/template/input.rs.tpl"
-    ))
-    .replace("{PATH}", &path)
-    .replace("{DAY}", &day)
+    let path = input.map(Path::new).unwrap_or(default);
+    // Read when running, from the directory of the generated crate
+    let path = fs::canonicalize(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?
+        .display()
+        .to_string();
+    Ok(template!(pm, "input.rs.tpl")?
+        .replace("{PATH}", &path.escape_default().to_string())
+        .replace("{DAY}", &day))
 }
@@ -9,3 +9,34 @@ pub fn args_without_aoc() -> impl Iterator<Item = OsString> {
         .filter(|(i, arg)| *i != 1 || arg != "aoc")
         .map(|(_, arg)| arg)
 }
+
+/// Parses a `NAME=VALUE` puzzle parameter, given with `--param`
+pub fn parse_param(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(format!("invalid parameter {:?}, expected NAME=VALUE", s)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params() {
+        assert_eq!(
+            parse_param("size=71").unwrap(),
+            ("size".to_string(), "71".to_string())
+        );
+        assert_eq!(
+            parse_param("expr=a=b").unwrap(),
+            ("expr".to_string(), "a=b".to_string())
+        );
+        assert_eq!(
+            parse_param("name=").unwrap(),
+            ("name".to_string(), String::new())
+        );
+        assert!(parse_param("=71").is_err());
+        assert!(parse_param("size").is_err());
+    }
+}
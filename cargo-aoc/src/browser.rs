@@ -0,0 +1,264 @@
+use cbc::cipher::{block_padding::Pkcs7, BlockDecryptMut, KeyIvInit};
+use clap::ValueEnum;
+use directories::BaseDirs;
+use rusqlite::{Connection, OpenFlags};
+use std::error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Browsers the session cookie can be imported from
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Browser {
+    Firefox,
+    /// Also covers Google Chrome
+    #[value(alias = "chrome")]
+    Chromium,
+}
+
+/// A session cookie found in a cookie database
+struct Cookie {
+    value: String,
+    /// When the cookie was last used, to pick the most recent login
+    last_access: i64,
+}
+
+impl Browser {
+    /// Finds the most recently used AoC session cookie in the profiles of the browser
+    pub fn session_cookie(self) -> Result<String, Box<dyn error::Error>> {
+        let databases = self.cookie_databases();
+        if databases.is_empty() {
+            return Err(format!("No {self:?} profile found").into());
+        }
+
+        let mut cookies = Vec::new();
+        for database in &databases {
+            let found = match self {
+                Browser::Firefox => read_database(database, firefox_cookie),
+                Browser::Chromium => read_database(database, chromium_cookie),
+            };
+            match found {
+                Ok(Some(cookie)) => cookies.push(cookie),
+                Ok(None) => {}
+                Err(e) => eprintln!("Skipping {}: {e}", database.display()),
+            }
+        }
+
+        cookies
+            .into_iter()
+            .max_by_key(|cookie| cookie.last_access)
+            .map(|cookie| cookie.value)
+            .ok_or_else(|| format!("No adventofcode.com session cookie found in {self:?}").into())
+    }
+
+    /// Lists the cookie databases of every profile of the browser
+    fn cookie_databases(self) -> Vec<PathBuf> {
+        let Some(dirs) = BaseDirs::new() else {
+            return Vec::new();
+        };
+
+        let (roots, files): (Vec<PathBuf>, &[&str]) = match self {
+            Browser::Firefox => (
+                vec![
+                    dirs.home_dir().join(".mozilla/firefox"),
+                    dirs.home_dir().join("snap/firefox/common/.mozilla/firefox"),
+                    dirs.config_dir().join("Mozilla/Firefox/Profiles"),
+                    dirs.data_dir().join("Firefox/Profiles"),
+                ],
+                &["cookies.sqlite"],
+            ),
+            Browser::Chromium => (
+                vec![
+                    dirs.config_dir().join("chromium"),
+                    dirs.config_dir().join("google-chrome"),
+                ],
+                &["Cookies", "Network/Cookies"],
+            ),
+        };
+
+        let mut databases = Vec::new();
+        for root in roots {
+            let Ok(profiles) = fs::read_dir(root) else {
+                continue;
+            };
+            for profile in profiles.flatten() {
+                for file in files {
+                    let database = profile.path().join(file);
+                    if database.is_file() {
+                        databases.push(database);
+                    }
+                }
+            }
+        }
+        databases
+    }
+}
+
+/// Looks for the AoC session cookie in a cookie database
+type CookieQuery = fn(&Connection) -> Result<Option<Cookie>, Box<dyn error::Error>>;
+
+/// Queries a copy of a cookie database, as browsers keep it locked while running
+fn read_database(
+    database: &Path,
+    query: CookieQuery,
+) -> Result<Option<Cookie>, Box<dyn error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let copy = dir.path().join("cookies.sqlite");
+    fs::copy(database, &copy)?;
+
+    // Recent changes may still be in the write-ahead log
+    let mut wal = database.as_os_str().to_owned();
+    wal.push("-wal");
+    if Path::new(&wal).exists() {
+        fs::copy(&wal, dir.path().join("cookies.sqlite-wal"))?;
+    }
+
+    let connection = Connection::open_with_flags(&copy, OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+    query(&connection)
+}
+
+fn firefox_cookie(connection: &Connection) -> Result<Option<Cookie>, Box<dyn error::Error>> {
+    let mut statement = connection.prepare(
+        "SELECT value, lastAccessed FROM moz_cookies \
+         WHERE host LIKE '%adventofcode.com' AND name = 'session' \
+         ORDER BY lastAccessed DESC LIMIT 1",
+    )?;
+    let mut rows = statement.query([])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(Cookie {
+            value: row.get(0)?,
+            last_access: row.get(1)?,
+        })),
+        None => Ok(None),
+    }
+}
+
+fn chromium_cookie(connection: &Connection) -> Result<Option<Cookie>, Box<dyn error::Error>> {
+    let mut statement = connection.prepare(
+        "SELECT value, encrypted_value, last_access_utc FROM cookies \
+         WHERE host_key LIKE '%adventofcode.com' AND name = 'session' \
+         ORDER BY last_access_utc DESC LIMIT 1",
+    )?;
+    let mut rows = statement.query([])?;
+    let Some(row) = rows.next()? else {
+        return Ok(None);
+    };
+
+    let value: String = row.get(0)?;
+    let encrypted: Vec<u8> = row.get(1)?;
+    let last_access: i64 = row.get(2)?;
+
+    let value = if !value.is_empty() {
+        value
+    } else {
+        let mut decrypted = chromium_decrypt(&encrypted)?;
+        // Since version 24 of the database, values are prefixed by the SHA256 of the domain
+        if chromium_version(connection)? >= 24 && decrypted.len() >= 32 {
+            decrypted.drain(..32);
+        }
+        String::from_utf8(decrypted)?
+    };
+
+    Ok(Some(Cookie { value, last_access }))
+}
+
+fn chromium_version(connection: &Connection) -> Result<u32, Box<dyn error::Error>> {
+    let version: String =
+        connection.query_row("SELECT value FROM meta WHERE key = 'version'", [], |row| {
+            row.get(0)
+        })?;
+    Ok(version.parse()?)
+}
+
+/// Decrypts a cookie Chromium encrypted without a keyring, with the well-known "peanuts" password
+fn chromium_decrypt(encrypted: &[u8]) -> Result<Vec<u8>, Box<dyn error::Error>> {
+    let Some(encrypted) = encrypted.strip_prefix(b"v10") else {
+        return Err(
+            "the cookie is encrypted with a key from the system keyring, which is not supported"
+                .into(),
+        );
+    };
+
+    let mut key = [0u8; 16];
+    pbkdf2::pbkdf2_hmac::<sha1::Sha1>(b"peanuts", b"saltysalt", 1, &mut key);
+    let iv = [b' '; 16];
+
+    let mut buffer = encrypted.to_vec();
+    let decrypted = cbc::Decryptor::<aes::Aes128>::new(&key.into(), &iv.into())
+        .decrypt_padded_mut::<Pkcs7>(&mut buffer)
+        .map_err(|_| "failed to decrypt the cookie")?;
+    Ok(decrypted.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a cookie database from one of the SQL fixtures
+    fn fixture(sql: &str) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let database = dir.path().join("Cookies");
+        Connection::open(&database)
+            .unwrap()
+            .execute_batch(sql)
+            .unwrap();
+        (dir, database)
+    }
+
+    fn session(sql: &str, query: CookieQuery) -> Option<String> {
+        let (_dir, database) = fixture(sql);
+        read_database(&database, query)
+            .unwrap()
+            .map(|cookie| cookie.value)
+    }
+
+    #[test]
+    fn firefox() {
+        let sql = include_str!("../tests/fixtures/firefox-cookies.sql");
+        assert_eq!(
+            session(sql, firefox_cookie).as_deref(),
+            Some("firefox-session")
+        );
+    }
+
+    #[test]
+    fn chromium_plaintext() {
+        let sql = include_str!("../tests/fixtures/chromium-plaintext.sql");
+        assert_eq!(
+            session(sql, chromium_cookie).as_deref(),
+            Some("plain-session")
+        );
+    }
+
+    #[test]
+    fn chromium_encrypted() {
+        let sql = include_str!("../tests/fixtures/chromium-v10.sql");
+        assert_eq!(
+            session(sql, chromium_cookie).as_deref(),
+            Some("chromium-session")
+        );
+    }
+
+    #[test]
+    fn chromium_encrypted_with_domain_hash() {
+        let sql = include_str!("../tests/fixtures/chromium-v24.sql");
+        assert_eq!(
+            session(sql, chromium_cookie).as_deref(),
+            Some("prefixed-session")
+        );
+    }
+
+    #[test]
+    fn no_session() {
+        let sql =
+            "CREATE TABLE moz_cookies (name TEXT, value TEXT, host TEXT, lastAccessed INTEGER);";
+        assert_eq!(session(sql, firefox_cookie), None);
+    }
+
+    #[test]
+    fn chromium_keyring() {
+        // Encrypted with a key from the keyring
+        assert!(chromium_decrypt(b"v11\x7f\x4d\x69\x98").is_err());
+        assert!(chromium_decrypt(b"v10 not a multiple of the block size").is_err());
+    }
+}
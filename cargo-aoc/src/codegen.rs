@@ -0,0 +1,537 @@
+use crate::date::AOCDate;
+use crate::network;
+use crate::project::{template, ProjectManager};
+use crate::puzzle::{PartExample, Puzzle};
+use directories::ProjectDirs;
+use reqwest::header::COOKIE;
+use std::cell::OnceCell;
+use std::error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::spanned::Spanned;
+use syn::{Item, ItemMod};
+
+/// Generates the boilerplate of a day, along with the extra files of the templates.
+///
+/// The day template is `src/day.rs.tpl` (`src/day/mod.rs.tpl` and `src/day/parse.rs.tpl` with the
+/// module layout), from the user's templates if they override it. Extra files are taken from the
+/// `extra` directory of the user's templates, e.g. `.aoc/templates/extra/benches/{DAY}.rs.tpl`
+/// generates `benches/day1.rs`.
+///
+/// The session token, if any, gives access to part two of the puzzle page once it is unlocked.
+pub fn codegen(
+    date: AOCDate,
+    pm: &ProjectManager,
+    session: Option<&str>,
+) -> Result<(), Box<dyn error::Error>> {
+    let renderer = Renderer {
+        pm,
+        date,
+        session,
+        puzzle: OnceCell::new(),
+    };
+
+    let dir = day_dir(pm, date);
+    let module = day_module(pm, date);
+
+    let mut files = if pm.config.layout.module {
+        let dir = dir.join(&module);
+        vec![
+            (dir.join("mod.rs"), template!(pm, "src/day/mod.rs.tpl")?),
+            (dir.join("parse.rs"), template!(pm, "src/day/parse.rs.tpl")?),
+        ]
+    } else {
+        vec![(
+            dir.join(format!("{module}.rs")),
+            template!(pm, "src/day.rs.tpl")?,
+        )]
+    };
+    if let Some(extra) = pm.find_template("extra") {
+        for (path, content) in extra_files(&extra, Path::new(""))? {
+            files.push((PathBuf::from(renderer.render(&path)), content));
+        }
+    }
+
+    for (filename, template) in files {
+        if filename.exists() {
+            eprintln!("{filename:?} already exists. Skipping...");
+            continue;
+        }
+        if let Some(dir) = filename.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&filename, renderer.render(&template))?;
+    }
+    Ok(())
+}
+
+/// The source directory of the crate
+fn src_dir(pm: &ProjectManager) -> &Path {
+    pm.lib_path
+        .as_deref()
+        .map(Path::new)
+        .and_then(|lib_path| lib_path.parent())
+        .unwrap_or(Path::new("src"))
+}
+
+/// The directory the module of a day is in, according to the layout
+fn day_dir(pm: &ProjectManager, date: AOCDate) -> PathBuf {
+    if pm.config.layout.year {
+        src_dir(pm).join(format!("year{}", date.year))
+    } else {
+        src_dir(pm).to_path_buf()
+    }
+}
+
+/// The name of the module of a day, according to the layout
+fn day_module(pm: &ProjectManager, date: AOCDate) -> String {
+    if pm.config.layout.padded {
+        format!("day{:02}", date.day)
+    } else {
+        format!("day{}", date.day)
+    }
+}
+
+/// Declares the module of a day in `lib.rs`, or in the module of its year with the year layout.
+pub fn update_lib_rs(date: AOCDate, pm: &ProjectManager) -> Result<(), Box<dyn error::Error>> {
+    let lib_rs_path = Path::new(pm.lib_path.as_deref().unwrap_or("src/lib.rs"));
+    if !lib_rs_path.exists() {
+        Err("lib.rs does not exist!")?
+    }
+
+    let module = day_module(pm, date);
+    let path = if pm.config.layout.year {
+        let year_module = format!("year{}", date.year);
+        declare_module(lib_rs_path, &year_module, "year", date.year as u32)?;
+
+        let year_rs_path = day_dir(pm, date).join("mod.rs");
+        if !year_rs_path.exists() {
+            fs::create_dir_all(day_dir(pm, date))?;
+            fs::write(&year_rs_path, "")?;
+        }
+        year_rs_path
+    } else {
+        lib_rs_path.to_path_buf()
+    };
+
+    if let Some(existing) = declare_module(&path, &module, "day", date.day)? {
+        eprintln!(
+            "{} already contains mod {existing}. Skipping...",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Declares a module like `mod day1;` in a file.
+///
+/// The declaration is inserted in order among the existing declarations of the same kind (e.g.
+/// `mod dayN;`), with the same visibility and indentation, or before `aoc_lib!` if there are none
+/// yet. The rest of the file is left untouched.
+///
+/// Returns the name of the existing declaration if there is already one.
+fn declare_module(
+    path: &Path,
+    name: &str,
+    prefix: &str,
+    number: u32,
+) -> Result<Option<String>, Box<dyn error::Error>> {
+    let lib_rs = fs::read_to_string(path)?;
+    let declared = with_module(&lib_rs, name, prefix, number)
+        .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+
+    match declared {
+        Declared::Already(existing) => Ok(Some(existing)),
+        Declared::Now(lib_rs) => {
+            fs::write(path, lib_rs)?;
+            Ok(None)
+        }
+    }
+}
+
+/// The outcome of declaring a module in a source file
+#[derive(Debug, PartialEq)]
+enum Declared {
+    /// The name of the existing declaration
+    Already(String),
+    /// The source with the new declaration
+    Now(String),
+}
+
+/// Declares a module in the source of a file, like [`declare_module`] does
+fn with_module(
+    lib_rs: &str,
+    name: &str,
+    prefix: &str,
+    number: u32,
+) -> Result<Declared, syn::Error> {
+    let file = syn::parse_file(lib_rs)?;
+
+    let days: Vec<(u32, &ItemMod)> = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Mod(module) => Some(module),
+            _ => None,
+        })
+        .filter_map(|module| Some((module_number(module, prefix)?, module)))
+        .collect();
+
+    // `day1` and `day01` are the same day
+    if let Some((_, module)) = days.iter().find(|(n, _)| *n == number) {
+        return Ok(Declared::Already(module.ident.to_string()));
+    }
+
+    let newline = if lib_rs.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let previous = days
+        .iter()
+        .filter(|(n, _)| *n < number)
+        .max_by_key(|(n, _)| *n);
+    let next = days
+        .iter()
+        .filter(|(n, _)| *n > number)
+        .min_by_key(|(n, _)| *n);
+
+    // (line to insert at, declaration neighbour, whether to leave a blank line after it)
+    let (line, neighbour, blank) = match (previous, next) {
+        (Some((_, module)), _) => (module.span().end().line, Some(*module), false),
+        (None, Some((_, module))) => (module.span().start().line - 1, Some(*module), false),
+        (None, None) => match file.items.iter().find(|item| is_aoc_lib(item)) {
+            Some(aoc_lib) => (aoc_lib.span().start().line - 1, None, true),
+            None => (lib_rs.lines().count(), None, false),
+        },
+    };
+
+    let (indent, vis) = match neighbour {
+        Some(module) => {
+            let start = module.span().start();
+            let line = lib_rs.lines().nth(start.line - 1).unwrap_or_default();
+            let indent: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+            let vis = match module.vis {
+                syn::Visibility::Inherited => String::new(),
+                ref vis => format!("{} ", quote_vis(vis)),
+            };
+            (indent, vis)
+        }
+        None => (String::new(), String::new()),
+    };
+
+    let mut declaration = format!("{indent}{vis}mod {name};{newline}");
+    if blank {
+        declaration += newline;
+    }
+
+    let mut lines: Vec<&str> = lib_rs.split_inclusive('\n').collect();
+    // The last line may have no line break yet
+    let last_line;
+    if line == lines.len() && !lib_rs.is_empty() && !lib_rs.ends_with('\n') {
+        last_line = format!("{}{newline}", lines.pop().unwrap_or_default());
+        lines.push(&last_line);
+    }
+    lines.insert(line.min(lines.len()), &declaration);
+
+    Ok(Declared::Now(lines.concat()))
+}
+
+/// The number of a declaration like `mod day1;` or `mod year2023;`
+fn module_number(module: &ItemMod, prefix: &str) -> Option<u32> {
+    let ident = module.ident.to_string();
+    let number = ident.strip_prefix(prefix)?;
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    number.parse().ok()
+}
+
+fn is_aoc_lib(item: &Item) -> bool {
+    match item {
+        Item::Macro(item) => item
+            .mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "aoc_lib"),
+        _ => false,
+    }
+}
+
+/// Writes a visibility back the way it is usually written, e.g. `pub(crate)`
+fn quote_vis(vis: &syn::Visibility) -> String {
+    match vis {
+        syn::Visibility::Public(_) => "pub".to_string(),
+        syn::Visibility::Restricted(restricted) => {
+            let path = restricted
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::");
+            match restricted.in_token {
+                Some(_) => format!("pub(in {path})"),
+                None => format!("pub({path})"),
+            }
+        }
+        syn::Visibility::Inherited => String::new(),
+    }
+}
+
+/// Lists the files of the `extra` template directory, with their `.tpl` extension stripped
+fn extra_files(
+    root: &Path,
+    relative: &Path,
+) -> Result<Vec<(String, String)>, Box<dyn error::Error>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(root.join(relative))? {
+        let path = relative.join(entry?.file_name());
+        if root.join(&path).is_dir() {
+            files.extend(extra_files(root, &path)?);
+        } else {
+            let content = fs::read_to_string(root.join(&path))?;
+            let path = path.to_string_lossy();
+            let path = path.strip_suffix(".tpl").unwrap_or(&path).to_string();
+            files.push((path, content));
+        }
+    }
+    Ok(files)
+}
+
+/// Fills the placeholders of templates, only reaching AoC when the puzzle page is needed
+struct Renderer<'a> {
+    pm: &'a ProjectManager,
+    date: AOCDate,
+    session: Option<&'a str>,
+    puzzle: OnceCell<Option<Puzzle>>,
+}
+
+impl Renderer<'_> {
+    fn render(&self, template: &str) -> String {
+        let date = self.date;
+        let mut rendered = template
+            .replace("{DAY}", &format!("day{}", date.day))
+            .replace("{MODULE}", &day_module(self.pm, date))
+            .replace("{DAY_NUM}", &date.day.to_string())
+            .replace("{DAY_PADDED}", &format!("{:02}", date.day))
+            .replace("{YEAR}", &date.year.to_string());
+
+        if rendered.contains("{TITLE}") {
+            let title = self.puzzle().and_then(|puzzle| puzzle.title.clone());
+            rendered = rendered.replace("{TITLE}", &title.unwrap_or_default());
+        }
+
+        for part in 1..=2 {
+            let example = format!("{{PART{part}_EXAMPLE}}");
+            let answer = format!("{{PART{part}_ANSWER}}");
+            if !rendered.contains(&example) && !rendered.contains(&answer) {
+                continue;
+            }
+
+            let PartExample {
+                input,
+                answer: expected,
+            } = self
+                .puzzle()
+                .map(|puzzle| puzzle.part(part))
+                .unwrap_or_default();
+            let input = match input {
+                Some(input) => raw_string(&input),
+                None => {
+                    format!("\"<EXAMPLE>\" /* TODO: couldn't find the example of part {part} */")
+                }
+            };
+            let expected = match expected {
+                Some(expected) => format!("{expected:?}"),
+                None => format!("\"<RESULT>\" /* TODO: couldn't find the answer of part {part} */"),
+            };
+            rendered = rendered
+                .replace(&example, &input)
+                .replace(&answer, &expected);
+        }
+        rendered
+    }
+
+    fn puzzle(&self) -> Option<&Puzzle> {
+        self.puzzle
+            .get_or_init(|| match puzzle_page(self.pm, self.date, self.session) {
+                Ok(page) => Some(Puzzle::parse(&page)),
+                Err(e) => {
+                    eprintln!(
+                        "Couldn't get the puzzle of {} day {}: {e}",
+                        self.date.year, self.date.day
+                    );
+                    None
+                }
+            })
+            .as_ref()
+    }
+}
+
+/// Writes a string as a raw string literal, with as many `#` as it needs
+fn raw_string(s: &str) -> String {
+    let mut hashes = String::new();
+    while s.contains(&format!("\"{hashes}")) {
+        hashes.push('#');
+    }
+    format!("r{hashes}\"{s}\"{hashes}")
+}
+
+/// Gets the page of a puzzle, from the cache or from AoC.
+/// Pages are fetched again until part two shows up in them.
+fn puzzle_page(
+    pm: &ProjectManager,
+    date: AOCDate,
+    session: Option<&str>,
+) -> Result<String, Box<dyn error::Error>> {
+    let cache = ProjectDirs::from("com.github", "gobanos", "cargo-aoc")
+        .map(|dirs| {
+            dirs.cache_dir()
+                .join("puzzles")
+                .join(date.year.to_string())
+                .join(format!("day{}.html", date.day))
+        })
+        .ok_or("Home directory path could not be retrieved from the operating system")?;
+    let cached = fs::read_to_string(&cache).ok();
+    if let Some(page) = &cached {
+        if Puzzle::parse(page).parts.len() >= 2 {
+            return Ok(page.clone());
+        }
+    }
+
+    let mut request = network::client(&pm.config)?.get(date.puzzle_url(&network::base_url()));
+    if let Some(session) = session {
+        request = request.header(COOKIE, format!("session={session}"));
+    }
+    let page = match request
+        .send()
+        .and_then(|response| response.error_for_status())
+    {
+        Ok(response) => response.text()?,
+        // A page without part two is better than none
+        Err(_) if cached.is_some() => return Ok(cached.unwrap_or_default()),
+        Err(e) => return Err(e.into()),
+    };
+
+    if let Some(dir) = cache.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&cache, &page)?;
+    Ok(page)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn declare_day(lib_rs: &str, day: u32) -> String {
+        match with_module(lib_rs, &format!("day{day}"), "day", day).unwrap() {
+            Declared::Now(lib_rs) => lib_rs,
+            Declared::Already(existing) => panic!("day{day} is already declared as {existing}"),
+        }
+    }
+
+    #[test]
+    fn in_order() {
+        assert_eq!(
+            declare_day("mod day1;\nmod day3;\n", 2),
+            "mod day1;\nmod day2;\nmod day3;\n"
+        );
+        assert_eq!(
+            declare_day("mod day2;\nmod day3;\n", 1),
+            "mod day1;\nmod day2;\nmod day3;\n"
+        );
+        // `day1` is not a prefix of `day12`
+        assert_eq!(
+            declare_day("mod day1;\nmod day12;\n", 11),
+            "mod day1;\nmod day11;\nmod day12;\n"
+        );
+        assert_eq!(
+            declare_day("mod day12;\nmod day2;\n", 13),
+            "mod day12;\nmod day13;\nmod day2;\n"
+        );
+    }
+
+    #[test]
+    fn already_declared() {
+        assert_eq!(
+            with_module("mod day01;\nmod day12;\n", "day1", "day", 1).unwrap(),
+            Declared::Already("day01".to_string())
+        );
+        assert!(matches!(
+            with_module("mod day1;\n", "day12", "day", 12).unwrap(),
+            Declared::Now(_)
+        ));
+        assert!(with_module("mod day1", "day2", "day", 2).is_err());
+    }
+
+    #[test]
+    fn like_its_neighbours() {
+        assert_eq!(
+            declare_day("pub mod day1;\n", 2),
+            "pub mod day1;\npub mod day2;\n"
+        );
+        assert_eq!(
+            declare_day("    pub(crate) mod day3;\n", 1),
+            "    pub(crate) mod day1;\n    pub(crate) mod day3;\n"
+        );
+    }
+
+    #[test]
+    fn around_other_items() {
+        let lib_rs = "use std::fmt;\n\nmod day1;\n#[cfg(test)]\nmod day3;\nmod utils;\n";
+        assert_eq!(
+            declare_day(lib_rs, 2),
+            "use std::fmt;\n\nmod day1;\nmod day2;\n#[cfg(test)]\nmod day3;\nmod utils;\n"
+        );
+
+        let lib_rs = "extern crate aoc_runner;\n\n#[macro_use]\nextern crate aoc_runner_derive;\n\
+                      \naoc_lib! { year = 2018 }\n";
+        assert_eq!(
+            declare_day(lib_rs, 1),
+            "extern crate aoc_runner;\n\n#[macro_use]\nextern crate aoc_runner_derive;\n\
+             \nmod day1;\n\naoc_lib! { year = 2018 }\n"
+        );
+    }
+
+    #[test]
+    fn line_endings() {
+        assert_eq!(
+            declare_day("mod day1;\r\nmod day3;\r\n", 2),
+            "mod day1;\r\nmod day2;\r\nmod day3;\r\n"
+        );
+        assert_eq!(declare_day("mod day1;", 2), "mod day1;\nmod day2;\n");
+        assert_eq!(declare_day("", 1), "mod day1;\n");
+    }
+
+    #[test]
+    fn examples_left_to_fill() {
+        let pm = ProjectManager {
+            name: "advent".to_string(),
+            slug: "advent".to_string(),
+            lib_path: None,
+            config: Config::default(),
+        };
+        let page = r#"<article class="day-desc"><h2>--- Day 3: Two ---</h2>
+            <pre><code>1</code></pre><pre><code>2</code></pre>
+            <p>The answer is <code><em>3</em></code></p></article>"#;
+        let renderer = Renderer {
+            pm: &pm,
+            date: AOCDate { day: 3, year: 2018 },
+            session: None,
+            puzzle: OnceCell::from(Some(Puzzle::parse(page))),
+        };
+
+        assert_eq!(
+            renderer.render("// {TITLE}\ncheck({PART1_EXAMPLE}, {PART1_ANSWER});"),
+            "// Two\ncheck(\"<EXAMPLE>\" /* TODO: couldn't find the example of part 1 */, \"3\");"
+        );
+        assert_eq!(
+            renderer.render("{PART2_ANSWER}"),
+            "\"<RESULT>\" /* TODO: couldn't find the answer of part 2 */"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use serde::Deserialize;
+use std::error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Project-level defaults for cargo-aoc.
+///
+/// Read from an `aoc.toml` file at the root of the project, or from the `[package.metadata.aoc]`
+/// table of `Cargo.toml`:
+/// ```toml
+/// [package.metadata.aoc]
+/// input-dir = "input"
+/// year = 2023
+/// input-store = true
+/// contact = "team@example.com"
+/// template-dir = ".aoc/templates"
+/// timeout = 30
+///
+/// [package.metadata.aoc.profile]
+/// codegen-units = 1
+///
+/// [package.metadata.aoc.bench]
+/// generator = true
+/// sample-size = 50
+///
+/// [package.metadata.aoc.layout]
+/// padded = true
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    /// Where inputs are downloaded
+    pub input_dir: PathBuf,
+    /// Default year of `cargo aoc input`
+    pub year: Option<u32>,
+    /// Download inputs to the per-user input store by default
+    pub input_store: bool,
+    /// Contact information sent to AoC in the user agent
+    pub contact: Option<String>,
+    /// Directory overriding the templates embedded in cargo-aoc
+    pub template_dir: Option<PathBuf>,
+    /// Timeout of network requests, in seconds
+    pub timeout: Option<u64>,
+    /// Settings added to the `[profile.release]` of the generated crates
+    pub profile: toml::Table,
+    pub bench: BenchConfig,
+    pub layout: Layout,
+}
+
+/// Defaults of `cargo aoc bench`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct BenchConfig {
+    /// Also benchmark generator functions
+    pub generator: bool,
+    /// Open the benchmark information in the browser
+    pub open: bool,
+    /// Criterion's sample size
+    pub sample_size: Option<usize>,
+    /// Criterion's measurement time, in seconds
+    pub measurement_time: Option<f64>,
+    /// Criterion's warm up time, in seconds
+    pub warm_up_time: Option<f64>,
+}
+
+/// Where the code of a day is generated
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Layout {
+    /// `day01` rather than `day1`
+    pub padded: bool,
+    /// `day1/mod.rs` along with a `parse.rs`, rather than `day1.rs`
+    pub module: bool,
+    /// Days of a year in a `year2023` module
+    pub year: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            input_dir: PathBuf::from("input"),
+            year: None,
+            input_store: false,
+            contact: None,
+            template_dir: None,
+            timeout: None,
+            profile: toml::Table::new(),
+            bench: BenchConfig::default(),
+            layout: Layout::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads `aoc.toml` if it exists, `[package.metadata.aoc]` otherwise
+    pub fn load(cargo: &toml::Value) -> Result<Config, Box<dyn error::Error>> {
+        let path = Path::new("aoc.toml");
+        if path.exists() {
+            return toml::from_str(&fs::read_to_string(path)?)
+                .map_err(|e| format!("Failed to parse aoc.toml: {e}").into());
+        }
+
+        match cargo
+            .get("package")
+            .and_then(|package| package.get("metadata"))
+            .and_then(|metadata| metadata.get("aoc"))
+        {
+            Some(aoc) => aoc
+                .clone()
+                .try_into()
+                .map_err(|e| format!("Failed to parse [package.metadata.aoc]: {e}").into()),
+            None => Ok(Config::default()),
+        }
+    }
+
+    pub fn user_agent(&self) -> String {
+        let contact = self
+            .contact
+            .as_deref()
+            .unwrap_or("gregory.obanos@gmail.com");
+        format!("github.com/gobanos/cargo-aoc by {contact}")
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+
+    /// The `[profile.release]` section of the generated crates
+    pub fn profile(&self, debug: bool) -> String {
+        let mut profile = self.profile.clone();
+        if debug {
+            profile.insert("debug".into(), true.into());
+        }
+
+        if profile.is_empty() {
+            String::new()
+        } else {
+            format!("[profile.release]\n{profile}")
+        }
+    }
+
+    /// Criterion settings, as calls on a `Criterion` builder
+    pub fn criterion(&self) -> String {
+        let mut config = String::new();
+        if let Some(size) = self.bench.sample_size {
+            config += &format!(".sample_size({size})");
+        }
+        if let Some(time) = self.bench.measurement_time {
+            config += &format!(".measurement_time(Duration::from_secs_f64({time:?}))");
+        }
+        if let Some(time) = self.bench.warm_up_time {
+            config += &format!(".warm_up_time(Duration::from_secs_f64({time:?}))");
+        }
+        config
+    }
+}
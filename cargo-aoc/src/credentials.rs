@@ -1,9 +1,90 @@
+use crate::store::DEFAULT_ACCOUNT;
+use chrono::{DateTime, Utc};
 use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Content of credentials.toml.
+///
+/// The session of the default account is kept at the top level, like older versions did:
+/// ```toml
+/// default = "work"
+/// session = "..."
+/// set_at = "2023-12-01T05:00:00Z"
+///
+/// [accounts.work]
+/// session_command = "pass show aoc/work"
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CredentialsFile {
+    /// Account used when none is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default: Option<String>,
+    #[serde(flatten)]
+    default_account: Account,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    accounts: BTreeMap<String, Account>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Account {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session: Option<String>,
+    /// When the session token was set, in RFC 3339 format
+    #[serde(skip_serializing_if = "Option::is_none")]
+    set_at: Option<String>,
+    /// Command printing the session token, used instead of `session`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_command: Option<String>,
+    /// Keys unknown to this version, kept as is
+    #[serde(flatten)]
+    extra: toml::Table,
+}
+
+impl Account {
+    fn is_empty(&self) -> bool {
+        self.session.is_none() && self.session_command.is_none() && self.extra.is_empty()
+    }
+}
+
+/// Environment variable taking precedence over every other source of session token
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// AoC session cookies last about a month
+const SESSION_LIFETIME_DAYS: i64 = 30;
+/// How long before the end of a session to start warning about it
+const SESSION_WARNING_DAYS: i64 = 5;
+
+/// Parses an account name, which is also used in input paths
+pub fn parse_account(s: &str) -> Result<String, String> {
+    if s.is_empty()
+        || !s
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Err(format!(
+            "invalid account name {:?}, only letters, digits, '-' and '_' are allowed",
+            s
+        ))
+    } else if s.chars().all(|c| c.is_ascii_digit()) {
+        // `input/{account}` would be the inputs of a year of the default account
+        Err(format!(
+            "invalid account name {:?}, it can't be only digits like a year",
+            s
+        ))
+    } else {
+        Ok(s.to_string())
+    }
+}
 
 pub struct CredentialsManager {
-    session_token: Option<String>,
+    credentials: CredentialsFile,
+    /// The account in use
+    account: String,
 }
 
 impl CredentialsManager {
@@ -12,7 +93,6 @@ impl CredentialsManager {
         let mut path_buf = ProjectDirs::from("com.github", "gobanos", "cargo-aoc")
             .map(|dirs| dirs.config_dir().to_path_buf())
             .expect("Home directory path could not be retrieved from the operating system");
-        fs::create_dir_all(path_buf.as_path()).expect("Config directory could not be created");
         path_buf.push("credentials.toml");
         path_buf
     }
@@ -24,8 +104,9 @@ impl CredentialsManager {
         path_buf
     }
 
-    /// Gets an instance of the CredentialManager
-    pub fn new() -> Self {
+    /// Gets an instance of the CredentialManager, for the given account
+    /// (or the default one if none is given)
+    pub fn new(account: Option<&str>) -> Self {
         // Gets a reference to the local credentials.toml file
         let path_buf = CredentialsManager::get_credentials_file();
 
@@ -33,6 +114,9 @@ impl CredentialsManager {
         if !path_buf.exists() {
             let old_path_buf = CredentialsManager::get_old_credentials_file();
             if old_path_buf.exists() {
+                if let Some(dir) = path_buf.parent() {
+                    fs::create_dir_all(dir).expect("Config directory could not be created");
+                }
                 // copy and delete, in case that they are on different file systems
                 fs::copy(old_path_buf.as_path(), path_buf.as_path())
                     .expect("Couldn't copy credentials to new location");
@@ -42,42 +126,239 @@ impl CredentialsManager {
         }
 
         // Reads it
-        let token: Option<String> = match fs::read_to_string(path_buf) {
-            // If we can read the TOML file
-            Ok(content) => {
-                // Parse the credentials.toml file
-                let creds: toml::Value = content.parse().expect("Failed to parse credentials.toml");
-                // Returns the parsed credentials' session value.
-                // (or None, if we did not get anything.)
-                creds["session"].as_str().map(|s| s.into())
-            }
-            // If we cant, just say that we did not get the token
-            Err(_) => None,
+        let credentials: CredentialsFile = match fs::read_to_string(path_buf) {
+            // If we can read the TOML file, parse it
+            Ok(content) => toml::from_str(&content).expect("Failed to parse credentials.toml"),
+            // If we cant, just say that we did not get any token
+            Err(_) => CredentialsFile::default(),
         };
 
+        let account = account
+            .or(credentials.default.as_deref())
+            .unwrap_or(DEFAULT_ACCOUNT)
+            .to_string();
+
         CredentialsManager {
-            session_token: token,
+            credentials,
+            account,
         }
     }
 
-    /// Attempt to get the session token of the user if it is referenced
-    /// in the credentials.toml file. Returns an error otherwise.
+    /// The account in use
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// The default account
+    pub fn default_account(&self) -> &str {
+        self.credentials
+            .default
+            .as_deref()
+            .unwrap_or(DEFAULT_ACCOUNT)
+    }
+
+    /// The credentials of the account in use, if any
+    fn current(&self) -> Option<&Account> {
+        if self.account == DEFAULT_ACCOUNT {
+            Some(&self.credentials.default_account)
+        } else {
+            self.credentials.accounts.get(&self.account)
+        }
+    }
+
+    /// Lists every account with a session token
+    pub fn accounts(&self) -> Vec<&str> {
+        let default = self
+            .credentials
+            .default_account
+            .session
+            .as_ref()
+            .map(|_| DEFAULT_ACCOUNT);
+        let named = self
+            .credentials
+            .accounts
+            .iter()
+            .filter(|(_, account)| account.session.is_some())
+            .map(|(name, _)| name.as_str());
+
+        default.into_iter().chain(named).collect()
+    }
+
+    /// Attempt to get the session token of the user, from the `AOC_SESSION` environment
+    /// variable, the `session_command` of credentials.toml or the credentials.toml file itself,
+    /// in that order. Returns an error otherwise.
     pub fn get_session_token(&self) -> Result<String, String> {
-        self.session_token
-            .clone()
-            .ok_or_else(|| "No session token available".into())
+        if let Ok(session) = env::var(SESSION_VAR) {
+            if !session.trim().is_empty() {
+                return Ok(session.trim().to_string());
+            }
+        }
+
+        let account = self.current();
+        if let Some(command) = account.and_then(|account| account.session_command.as_deref()) {
+            return run_session_command(command);
+        }
+
+        let session = account.and_then(|account| account.session.clone());
+        let session = session
+            .ok_or_else(|| format!("No session token available for account {}", self.account))?;
+
+        self.warn_if_expiring();
+        Ok(session)
+    }
+
+    /// Whether the session token in use is the one of credentials.toml, rather than the one of
+    /// `AOC_SESSION` or of the `session_command`
+    fn token_from_file(&self) -> bool {
+        let from_var = env::var(SESSION_VAR).is_ok_and(|session| !session.trim().is_empty());
+        !from_var
+            && self
+                .current()
+                .is_some_and(|account| account.session_command.is_none())
+    }
+
+    /// How many days ago the session token in use was set, if it comes from credentials.toml
+    pub fn session_age(&self) -> Option<i64> {
+        if !self.token_from_file() {
+            return None;
+        }
+        let set_at = self.current()?.set_at.as_deref()?;
+        let set_at = DateTime::parse_from_rfc3339(set_at).ok()?;
+        Some((Utc::now() - set_at.with_timezone(&Utc)).num_days())
+    }
+
+    /// Warns when the session token is about to expire
+    fn warn_if_expiring(&self) {
+        match self.session_age() {
+            Some(age) if age >= SESSION_LIFETIME_DAYS => eprintln!(
+                "Warning: the session token of account {} was set {} days ago and has probably expired",
+                self.account, age
+            ),
+            Some(age) if age >= SESSION_LIFETIME_DAYS - SESSION_WARNING_DAYS => eprintln!(
+                "Warning: the session token of account {} was set {} days ago, AoC sessions last about {} days",
+                self.account, age, SESSION_LIFETIME_DAYS
+            ),
+            _ => {}
+        }
     }
 
     /// Attempt to set the session token of the user in credentials.toml
     /// Returns an error in case of an IO error or something ...
-    pub fn set_session_token(&mut self, token: String) -> Result<(), std::io::Error> {
-        // Gets a reference to the local credentials.toml file
-        let toml_str = format!("session = \"{}\"", token);
+    pub fn set_session_token(&mut self, token: String) -> Result<(), Box<dyn std::error::Error>> {
         // Sets the information of this struct
-        self.session_token = Some(token);
+        let account = if self.account == DEFAULT_ACCOUNT {
+            &mut self.credentials.default_account
+        } else {
+            self.credentials
+                .accounts
+                .entry(self.account.clone())
+                .or_default()
+        };
+        account.session = Some(token);
+        account.set_at = Some(Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+
+        self.save()
+    }
+
+    /// Deletes the session token of the account in use from credentials.toml
+    pub fn unset_session_token(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.account == DEFAULT_ACCOUNT {
+            let account = &mut self.credentials.default_account;
+            account.session = None;
+            account.set_at = None;
+        } else if let Some(account) = self.credentials.accounts.get_mut(&self.account) {
+            account.session = None;
+            account.set_at = None;
+            if account.is_empty() {
+                self.credentials.accounts.remove(&self.account);
+            }
+        }
+
+        self.save()
+    }
+
+    /// Makes the account in use the default one
+    pub fn set_default(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.credentials.default = if self.account == DEFAULT_ACCOUNT {
+            None
+        } else {
+            Some(self.account.clone())
+        };
+
+        self.save()
+    }
+
+    /// Writes the credentials to credentials.toml
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let toml_str = toml::to_string(&self.credentials)?;
         let path_buf = CredentialsManager::get_credentials_file();
+        if let Some(dir) = path_buf.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        // Writes the session tokens to the file, only readable by its owner
+        write_private(&path_buf, &toml_str)?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, content: &str) -> std::io::Result<()> {
+    use std::fs::{OpenOptions, Permissions};
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // The mode only applies to new files
+    fs::set_permissions(path, Permissions::from_mode(0o600))?;
+    file.write_all(content.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, content: &str) -> std::io::Result<()> {
+    fs::write(path, content)
+}
+
+/// Runs a command printing the session token, like `pass show aoc`
+fn run_session_command(command: &str) -> Result<String, String> {
+    let output = if cfg!(windows) {
+        Command::new("cmd").args(["/C", command]).output()
+    } else {
+        Command::new("sh").args(["-c", command]).output()
+    }
+    .map_err(|e| format!("Failed to run session command `{}`: {}", command, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Session command `{}` failed with code {}",
+            command,
+            output.status.code().unwrap_or(-1)
+        ));
+    }
+
+    let session = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if session.is_empty() {
+        return Err(format!("Session command `{}` printed nothing", command));
+    }
+    Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // Writes the session token to the file
-        fs::write(path_buf, toml_str)
+    #[test]
+    fn account_names() {
+        assert_eq!(parse_account("work").unwrap(), "work");
+        assert_eq!(parse_account("alt-2023_b").unwrap(), "alt-2023_b");
+        assert!(parse_account("").is_err());
+        assert!(parse_account("../up").is_err());
+        assert!(parse_account("my account").is_err());
+        assert!(parse_account("2023").is_err());
     }
 }
@@ -1,8 +1,14 @@
 use chrono::prelude::*;
 use chrono_tz::EST;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use crate::Input;
 
+/// The first year Advent of Code took place
+const FIRST_YEAR: u32 = 2015;
+
 #[derive(Debug, Clone, Copy)]
 pub struct AOCDate {
     /// The day of the input to retrieve
@@ -11,35 +17,235 @@ pub struct AOCDate {
     pub year: i32,
 }
 
+/// A sorted set of years or days given on the command line.
+///
+/// Accepts single values (`5`), ranges (`5-9`, `5..9`, `5..=9`) and comma separated lists of
+/// those (`1,3,5-9`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection(Vec<u32>);
+
+impl Selection {
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |n: &str| {
+            n.trim()
+                .parse::<u32>()
+                .map_err(|e| format!("Failed to parse {}: {:?}", n.trim(), e))
+        };
+
+        let mut values = BTreeSet::new();
+        for item in s.split(',') {
+            let (start, end) = if let Some((start, end)) = item.split_once("..=") {
+                (parse(start)?, parse(end)?)
+            } else if let Some((start, end)) = item.split_once("..") {
+                let end = parse(end)?;
+                if end == 0 {
+                    return Err(format!("{} is an empty range", item));
+                }
+                (parse(start)?, end - 1)
+            } else if let Some((start, end)) = item.split_once('-') {
+                (parse(start)?, parse(end)?)
+            } else {
+                let value = parse(item)?;
+                (value, value)
+            };
+
+            if start > end {
+                return Err(format!("{} is an empty range", item));
+            }
+            values.extend(start..=end);
+        }
+
+        Ok(Selection(values.into_iter().collect()))
+    }
+}
+
+/// Parses a day selection, ensuring every day is between 1 and 25
+pub fn parse_days(s: &str) -> Result<Selection, String> {
+    let days: Selection = s.parse()?;
+    if let Some(d) = days.iter().find(|&d| d == 0 || d > 25) {
+        return Err(format!("day {} is not between 1 and 25", d));
+    }
+    Ok(days)
+}
+
+/// Parses a year selection, ensuring no year predates the first Advent of Code
+pub fn parse_years(s: &str) -> Result<Selection, String> {
+    let years: Selection = s.parse()?;
+    if let Some(y) = years.iter().find(|&y| y < FIRST_YEAR) {
+        return Err(format!("there was no Advent of Code in {}", y));
+    }
+    Ok(years)
+}
+
+/// Get the current date in the EST timezone, which is used by advent of code to
+/// release new puzzles.
+fn today() -> DateTime<chrono_tz::Tz> {
+    EST.from_utc_datetime(&Utc::now().naive_utc())
+}
+
+/// The current year, in the timezone Advent of Code uses
+pub fn current_year() -> u32 {
+    today().year() as u32
+}
+
 impl AOCDate {
-    pub fn new(matches: &Input) -> Self {
-        // Get the current date in the EST timezone, which is used by advent of code to
-        // release new puzzles.
-        let utc_today = Utc::now().naive_utc();
-        let today = EST.from_utc_datetime(&utc_today);
-        let day: u32 = matches
-            .day
-            .map(|d| d.0 as u32)
-            .unwrap_or_else(|| today.day());
+    /// Lists every date matching the arguments of the "input" subcommand.
+    ///
+    /// Without `--year`, the project's default year is used if any, otherwise `--all` selects
+    /// every event that has already started. With `--all`, puzzles that are not released yet are
+    /// left out. Days an event doesn't have are an error for a single year, and skipped otherwise.
+    pub fn select(matches: &Input, default_year: Option<u32>) -> Result<Vec<Self>, String> {
+        let today = today();
+        let years: Vec<u32> = match (&matches.year, default_year) {
+            (Some(years), _) => years.iter().collect(),
+            (None, Some(year)) => vec![year],
+            (None, None) if matches.all => {
+                let last = if today.month() == 12 {
+                    today.year()
+                } else {
+                    today.year() - 1
+                };
+                (FIRST_YEAR..=last as u32).collect()
+            }
+            (None, None) => vec![today.year() as u32],
+        };
+
+        // Over several years, the days of a longer event are skipped for the shorter ones
+        let single_year = years.len() == 1;
+        let mut dates = Vec::new();
+        for year in years {
+            let year = year as i32;
+            let days: Vec<u32> = match &matches.day {
+                Some(days) => days.iter().collect(),
+                None if matches.all => (1..=AOCDate::last_day(year)).collect(),
+                None => vec![today.day()],
+            };
+
+            for day in days {
+                if day > AOCDate::last_day(year) {
+                    if !single_year {
+                        continue;
+                    }
+                    return Err(format!(
+                        "Advent of Code {} only has {} days",
+                        year,
+                        AOCDate::last_day(year)
+                    ));
+                }
 
-        let year: i32 = matches.year.unwrap_or_else(|| today.year());
+                let date = AOCDate { day, year };
+                if !matches.all || date.is_released() {
+                    dates.push(date);
+                }
+            }
+        }
 
-        AOCDate { day, year }
+        Ok(dates)
     }
 
-    pub fn directory(&self) -> String {
-        format!("input/{}", self.year)
+    /// The last puzzle of a given event (events have 12 days since 2025)
+    pub fn last_day(year: i32) -> u32 {
+        if year >= 2025 {
+            12
+        } else {
+            25
+        }
     }
 
-    pub fn filename(&self) -> String {
-        format!("input/{}/day{}.txt", self.year, self.day)
+    /// Puzzles unlock at midnight EST
+    pub fn is_released(&self) -> bool {
+        EST.with_ymd_and_hms(self.year, 12, self.day, 0, 0, 0)
+            .single()
+            .is_some_and(|release| release <= today())
     }
 
-    /// Consumes the date to get an URL
-    pub fn request_url(&self) -> String {
-        format!(
-            "https://adventofcode.com/{}/day/{}/input",
-            self.year, self.day
-        )
+    pub fn directory(&self, input_dir: &Path) -> PathBuf {
+        input_dir.join(self.year.to_string())
+    }
+
+    pub fn filename(&self, input_dir: &Path) -> PathBuf {
+        self.directory(input_dir)
+            .join(format!("day{}.txt", self.day))
+    }
+
+    pub fn example_filename(&self, input_dir: &Path, index: u32) -> PathBuf {
+        self.directory(input_dir)
+            .join(format!("day{}.example{}.txt", self.day, index))
+    }
+
+    pub fn example_metadata_filename(&self, input_dir: &Path, index: u32) -> PathBuf {
+        self.directory(input_dir)
+            .join(format!("day{}.example{}.toml", self.day, index))
+    }
+
+    /// The URL of the puzzle page, on the server at `base_url`
+    pub fn puzzle_url(&self, base_url: &str) -> String {
+        format!("{}/{}/day/{}", base_url, self.year, self.day)
+    }
+
+    /// The URL of the input, on the server at `base_url`
+    pub fn request_url(&self, base_url: &str) -> String {
+        format!("{}/{}/day/{}/input", base_url, self.year, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(s: &str) -> Result<Vec<u32>, String> {
+        s.parse::<Selection>()
+            .map(|selection| selection.iter().collect())
+    }
+
+    #[test]
+    fn selections() {
+        assert_eq!(values("5").unwrap(), [5]);
+        assert_eq!(values("5-9").unwrap(), [5, 6, 7, 8, 9]);
+        assert_eq!(values("5..9").unwrap(), [5, 6, 7, 8]);
+        assert_eq!(values("5..=9").unwrap(), [5, 6, 7, 8, 9]);
+        assert_eq!(values("9, 1,3-4,3..5").unwrap(), [1, 3, 4, 9]);
+        assert_eq!(values("5..6").unwrap(), [5]);
+    }
+
+    #[test]
+    fn invalid_selections() {
+        assert!(values("5..5").is_err());
+        assert!(values("0..0").is_err());
+        assert!(values("9-5").is_err());
+        assert!(values("9..=5").is_err());
+        assert!(values("").is_err());
+        assert!(values("1,,3").is_err());
+        assert!(values("five").is_err());
+    }
+
+    fn input(years: &str, days: &str) -> Input {
+        Input {
+            day: Some(days.parse().unwrap()),
+            year: Some(years.parse().unwrap()),
+            all: false,
+            generate: false,
+            store: false,
+            export: false,
+            link: false,
+            force: false,
+        }
+    }
+
+    #[test]
+    fn days_past_the_end_of_an_event() {
+        let dates = AOCDate::select(&input("2024..=2025", "12-14"), None).unwrap();
+        let dates: Vec<_> = dates.iter().map(|date| (date.year, date.day)).collect();
+        assert_eq!(dates, [(2024, 12), (2024, 13), (2024, 14), (2025, 12)]);
+
+        assert!(AOCDate::select(&input("2025", "12-14"), None).is_err());
     }
 }
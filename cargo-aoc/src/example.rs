@@ -0,0 +1,115 @@
+use crate::date::AOCDate;
+use aoc_runner_internal::Part;
+use std::error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An example input, stored next to the real input as `input/{year}/day{N}.example{K}.txt`.
+///
+/// Expected answers, and the parameters of the solutions for the example, can be given in an
+/// optional `input/{year}/day{N}.example{K}.toml`:
+/// ```toml
+/// part1 = 42
+/// part2 = "ABCD"
+///
+/// [params]
+/// size = 7
+/// ```
+#[derive(Debug)]
+pub struct Example {
+    pub input: PathBuf,
+    metadata: toml::Table,
+}
+
+impl Example {
+    pub fn load(
+        date: &AOCDate,
+        input_dir: &Path,
+        index: u32,
+    ) -> Result<Self, Box<dyn error::Error>> {
+        let input = date.example_filename(input_dir, index);
+        if !input.exists() {
+            return Err(format!("Example input {} not found", input.display()).into());
+        }
+
+        let metadata_filename = date.example_metadata_filename(input_dir, index);
+        let metadata = match fs::read_to_string(&metadata_filename) {
+            Ok(content) => content
+                .parse()
+                .map_err(|e| format!("Failed to parse {}: {e}", metadata_filename.display()))?,
+            Err(_) => toml::Table::new(),
+        };
+
+        Ok(Example { input, metadata })
+    }
+
+    /// The expected answer for a given part, if any
+    pub fn answer(&self, part: Part) -> Option<String> {
+        match self.metadata.get(&format!("part{}", part.0))? {
+            toml::Value::String(s) => Some(s.clone()),
+            value => Some(value.to_string()),
+        }
+    }
+
+    /// The parameters of the solutions for this example, from its `[params]` table
+    pub fn params(&self) -> Result<Vec<(String, String)>, Box<dyn error::Error>> {
+        let params = match self.metadata.get("params") {
+            Some(toml::Value::Table(params)) => params,
+            Some(_) => return Err("`params` of an example must be a table".into()),
+            None => return Ok(Vec::new()),
+        };
+
+        Ok(params
+            .iter()
+            .map(|(name, value)| match value {
+                toml::Value::String(s) => (name.clone(), s.clone()),
+                value => (name.clone(), value.to_string()),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::example_params;
+
+    fn example(metadata: &str) -> Example {
+        Example {
+            input: PathBuf::from("day1.example1.txt"),
+            metadata: metadata.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn params() {
+        let example = example("part1 = 42\n[params]\nsize = 7\nname = \"abc\"\n");
+        assert_eq!(
+            example.params().unwrap(),
+            [
+                ("name".to_string(), "abc".to_string()),
+                ("size".to_string(), "7".to_string()),
+            ]
+        );
+
+        assert!(self::example("part1 = 42").params().unwrap().is_empty());
+        assert!(self::example("params = 7").params().is_err());
+    }
+
+    #[test]
+    fn cli_params_come_last() {
+        let example = example("[params]\nsize = 7\nsteps = 12\n");
+        let cli = [("size".to_string(), "71".to_string())];
+
+        // The last value of a parameter wins, so `--param` beats the example
+        assert_eq!(
+            example_params(Some(&example), &cli).unwrap(),
+            [
+                ("size".to_string(), "7".to_string()),
+                ("steps".to_string(), "12".to_string()),
+                ("size".to_string(), "71".to_string()),
+            ]
+        );
+        assert_eq!(example_params(None, &cli).unwrap(), cli);
+    }
+}
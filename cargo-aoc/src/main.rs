@@ -1,15 +1,28 @@
 mod app;
 mod args;
+mod browser;
+mod codegen;
+mod config;
 mod credentials;
 mod date;
+mod example;
+mod network;
 mod project;
+mod puzzle;
+mod scaffold;
 mod errors;
+mod store;
 
 use aoc_runner_internal::{Day, Part};
-use app::{execute_bench, execute_credentials, execute_default, execute_input};
+use app::{
+    execute_bench, execute_credentials, execute_default, execute_init, execute_input, execute_new,
+};
 
 use crate::args::args_without_aoc;
+use crate::browser::Browser;
+use crate::date::Selection;
 use clap::Parser;
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[clap(
@@ -33,10 +46,30 @@ pub struct Cli {
     #[clap(long)]
     profile: bool,
 
+    /// Features of the crate to enable, e.g. to run solutions under `#[cfg(feature = "slow")]`.
+    #[clap(short = 'F', long, value_delimiter = ',')]
+    features: Vec<String>,
+
     /// Generate the boilerplate for the given day.
     #[clap(long, short)]
     generate: bool,
 
+    /// Download missing inputs to the per-user input store rather than to `input/`.
+    #[clap(long)]
+    store: bool,
+
+    /// Use the K-th example input (`input/{year}/dayN.exampleK.txt`) instead of the real one.
+    #[clap(short, long, value_name = "K", num_args = 0..=1, default_missing_value = "1", conflicts_with = "input")]
+    example: Option<u32>,
+
+    /// Overrides a parameter of the solutions, like `--param size=7`. Can be repeated.
+    #[clap(long = "param", value_name = "NAME=VALUE", value_parser = args::parse_param)]
+    params: Vec<(String, String)>,
+
+    /// Use the given AoC account. Defaults to the default account.
+    #[clap(long, global = true, value_parser = credentials::parse_account)]
+    account: Option<String>,
+
     #[clap(subcommand)]
     subcmd: Option<SubCommands>,
 }
@@ -46,6 +79,8 @@ enum SubCommands {
     Bench(Bench),
     Credentials(Credentials),
     Input(Input),
+    New(New),
+    Init(Init),
 }
 
 /// Runs the benchmark for the last day (or a given day)
@@ -74,48 +109,143 @@ pub struct Bench {
     /// Add debug info for profiling tools.
     #[clap(long)]
     profile: bool,
+
+    /// Features of the crate to enable, e.g. to run solutions under `#[cfg(feature = "slow")]`.
+    #[clap(short = 'F', long, value_delimiter = ',')]
+    features: Vec<String>,
+
+    /// Download missing inputs to the per-user input store rather than to `input/`.
+    #[clap(long)]
+    store: bool,
+
+    /// Use the K-th example input (`input/{year}/dayN.exampleK.txt`) instead of the real one.
+    #[clap(short, long, value_name = "K", num_args = 0..=1, default_missing_value = "1", conflicts_with = "input")]
+    example: Option<u32>,
+
+    /// Overrides a parameter of the solutions, like `--param size=7`. Can be repeated.
+    #[clap(long = "param", value_name = "NAME=VALUE", value_parser = args::parse_param)]
+    params: Vec<(String, String)>,
 }
 
-/// Sets the session cookie
+/// Shows or sets the session cookie (`cargo aoc credentials <TOKEN>` sets it)
 #[derive(Parser, Debug)]
 pub struct Credentials {
-    set: Option<String>,
+    /// Checks that the session token is valid, and which user it belongs to
+    #[clap(long)]
+    check: bool,
+
+    /// Shows the whole session token rather than a masked one
+    #[clap(long)]
+    show: bool,
+
+    /// Deletes the session token
+    #[clap(long, visible_alias = "remove", conflicts_with_all = ["check", "show"])]
+    unset: bool,
+
+    /// Imports the session token from a local browser profile
+    #[clap(long, value_enum, value_name = "BROWSER", conflicts_with = "unset")]
+    from_browser: Option<Browser>,
+
+    #[clap(subcommand)]
+    action: Option<CredentialsAction>,
+}
+
+#[derive(Parser, Debug)]
+pub enum CredentialsAction {
+    /// Sets the session token of the account
+    Set { token: String },
+    /// Makes the account the default one
+    Default,
+    /// Lists the accounts
+    List,
+    /// `cargo aoc credentials <TOKEN>`, same as `set`
+    #[clap(external_subcommand)]
+    Token(Vec<String>),
 }
 
 /// Downloads the input for today (or a given day)
 #[derive(Parser, Debug)]
 pub struct Input {
-    /// Specifies the day. Defaults to today's date.
-    #[clap(short, long)]
-    day: Option<Day>,
+    /// Specifies the day(s), e.g. `5` or `1,3,5-9`. Defaults to today's date.
+    #[clap(short, long, value_parser = date::parse_days)]
+    day: Option<Selection>,
 
-    /// Specifies the year. Defaults to the current year.
-    #[clap(short, long)]
-    year: Option<i32>,
+    /// Specifies the year(s), e.g. `2023` or `2015..=2023`. Defaults to the current year.
+    #[clap(short, long, value_parser = date::parse_years)]
+    year: Option<Selection>,
 
-    /// Downloads all possible inputs for a given year
+    /// Downloads all released inputs for the given years (every event when no year is given)
     #[clap(short, long)]
     all: bool,
 
     /// Generate the boilerplate for the given day.
     #[clap(long, short)]
     generate: bool,
+
+    /// Download inputs to the per-user input store rather than to `input/`.
+    #[clap(long)]
+    store: bool,
+
+    /// Copy inputs from the input store to `input/`.
+    #[clap(long, conflicts_with = "link")]
+    export: bool,
+
+    /// Link inputs from the input store into `input/`.
+    #[clap(long)]
+    link: bool,
+
+    /// Download inputs again, replacing the existing ones.
+    #[clap(short, long)]
+    force: bool,
+}
+
+/// Creates a new aoc-runner project
+#[derive(Parser, Debug)]
+pub struct New {
+    /// Where to create the project
+    path: PathBuf,
+
+    /// The year of the puzzles. Defaults to the current year.
+    #[clap(short, long)]
+    year: Option<u32>,
+
+    /// Generate the boilerplate for day 1.
+    #[clap(long, short)]
+    generate: bool,
+}
+
+/// Turns an existing crate (or directory) into an aoc-runner project
+#[derive(Parser, Debug)]
+pub struct Init {
+    /// The crate to convert. Defaults to the current directory.
+    path: Option<PathBuf>,
+
+    /// The year of the puzzles. Defaults to the current year.
+    #[clap(short, long)]
+    year: Option<u32>,
+
+    /// Generate the boilerplate for day 1.
+    #[clap(long, short)]
+    generate: bool,
 }
 
 fn main() {
     let cli = Cli::parse_from(args_without_aoc());
 
-    let Some(subcommand) = cli.subcmd else {
+    let account = cli.account.as_deref();
+    let Some(subcommand) = &cli.subcmd else {
         return execute_default(&cli).unwrap();
     };
 
     match subcommand {
-        SubCommands::Bench(arg) => execute_bench(&arg),
+        SubCommands::Bench(arg) => execute_bench(arg, account),
         SubCommands::Credentials(arg) => {
-            execute_credentials(&arg);
+            execute_credentials(arg, account);
             Ok(())
         }
-        SubCommands::Input(arg) => execute_input(&arg),
+        SubCommands::Input(arg) => execute_input(arg, account),
+        SubCommands::New(arg) => execute_new(arg, account),
+        SubCommands::Init(arg) => execute_init(arg, account),
     }
     .unwrap()
 }
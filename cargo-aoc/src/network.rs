@@ -0,0 +1,146 @@
+use crate::config::Config;
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE, USER_AGENT};
+use reqwest::StatusCode;
+use std::env;
+use std::error::Error;
+
+/// Where Advent of Code is served from
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable overriding the base URL, e.g. to test against a local server
+pub const BASE_URL_VAR: &str = "CARGO_AOC_BASE_URL";
+
+/// The base URL of every request, without a trailing slash.
+///
+/// It's read once by commands, and given to the functions making requests.
+pub fn base_url() -> String {
+    match env::var(BASE_URL_VAR) {
+        Ok(url) if !url.is_empty() => url.trim_end_matches('/').to_string(),
+        _ => DEFAULT_BASE_URL.to_string(),
+    }
+}
+
+/// The headers of every request: the user agent identifying cargo-aoc and its user
+fn default_headers(config: &Config) -> Result<HeaderMap, Box<dyn Error>> {
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_str(&config.user_agent())?);
+    Ok(headers)
+}
+
+/// The HTTP client for AoC, with the user agent and the timeout of the config
+pub fn client(config: &Config) -> Result<reqwest::blocking::Client, Box<dyn Error>> {
+    let mut client = reqwest::blocking::Client::builder().default_headers(default_headers(config)?);
+    if let Some(timeout) = config.timeout() {
+        client = client.timeout(timeout);
+    }
+    Ok(client.build()?)
+}
+
+/// Like [`client`], for concurrent downloads
+pub fn async_client(config: &Config) -> Result<reqwest::Client, Box<dyn Error>> {
+    let mut client = reqwest::Client::builder().default_headers(default_headers(config)?);
+    if let Some(timeout) = config.timeout() {
+        client = client.timeout(timeout);
+    }
+    Ok(client.build()?)
+}
+
+/// Asks AoC who a session token belongs to, `None` if it is invalid or expired
+pub fn session_user(
+    token: &str,
+    base_url: &str,
+    config: &Config,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let response = client(config)?
+        .get(format!("{}/", base_url))
+        .header(COOKIE, format!("session={}", token))
+        .send()?;
+
+    if response.status() != StatusCode::OK {
+        return Err(format!("Could not reach AoC. Status: {}", response.status()).into());
+    }
+
+    Ok(logged_in_user(&response.text()?))
+}
+
+/// Finds the name of the logged in user in an AoC page, if any
+pub fn logged_in_user(page: &str) -> Option<String> {
+    let start = page.find(r#"<div class="user">"#)? + r#"<div class="user">"#.len();
+    let name = page[start..].split('<').next()?.trim();
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    const LOGGED_IN: &str = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><nav><ul><li><a href="/2023/about">[About]</a></li></ul></nav><div class="user">octocat <span class="star-count">42*</span></div></div></header>"#;
+    const LOGGED_OUT: &str = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><nav><ul><li><a href="/2023/auth/login">[Log In]</a></li></ul></nav></div></header>"#;
+
+    #[test]
+    fn user_of_a_page() {
+        assert_eq!(logged_in_user(LOGGED_IN).as_deref(), Some("octocat"));
+        assert_eq!(logged_in_user(LOGGED_OUT), None);
+        assert_eq!(logged_in_user(r#"<div class="user"> </div>"#), None);
+    }
+
+    /// Serves `requests` requests like AoC would: the page of a logged in user for the `good`
+    /// session token, an error for the `down` one and the page of a visitor for any other
+    fn stand_in_server(requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut cookie = None;
+                for line in BufReader::new(&stream).lines() {
+                    let line = line.unwrap();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("cookie") {
+                            cookie = Some(value.trim().to_string());
+                        }
+                    }
+                }
+
+                let (status, body) = match cookie.as_deref() {
+                    Some("session=good") => ("200 OK", LOGGED_IN),
+                    Some("session=down") => ("500 Internal Server Error", ""),
+                    _ => ("200 OK", LOGGED_OUT),
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn session_user_from_server() {
+        let url = stand_in_server(3);
+        let config = Config::default();
+
+        assert_eq!(
+            session_user("good", &url, &config).unwrap().as_deref(),
+            Some("octocat")
+        );
+        assert_eq!(session_user("expired", &url, &config).unwrap(), None);
+        assert!(session_user("down", &url, &config).is_err());
+    }
+}
@@ -1,14 +1,30 @@
-use aoc_runner_internal::DayParts;
+use aoc_runner_internal::{DayPart, DayParts, DayPartsBuilder, Part};
+use directories::ProjectDirs;
 use std::error;
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::process;
+use crate::config::Config;
 use crate::errors::CouldNotLoadDayParts;
 
+/// Loads one of the templates embedded in cargo-aoc, unless the user overrides it
+macro_rules! template {
+    ($pm:expr, $name:literal) => {
+        $pm.template(
+            $name,
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/template/", $name)),
+        )
+    };
+}
+
+pub(crate) use template;
+
 #[derive(Clone, Debug)]
 pub struct ProjectManager {
     pub name: String,
     pub slug: String,
     pub lib_path: Option<String>,
+    pub config: Config,
 }
 
 impl ProjectManager {
@@ -32,17 +48,70 @@ impl ProjectManager {
             .and_then(|lib_path| lib_path.as_str())
             .map(String::from);
 
+        let config = Config::load(&cargo)?;
+
         Ok(ProjectManager {
             name: crate_name,
             slug: crate_slug,
             lib_path,
+            config,
         })
     }
 
-    pub fn build_project(&self) -> Result<DayParts, Box<dyn error::Error>> {
-        let args = vec!["check", "--color=always"];
+    /// Directories user templates are looked for in, by order of priority.
+    ///
+    /// They all have the layout of the embedded templates, e.g. `src/day.rs.tpl`.
+    fn template_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = self.config.template_dir.iter().cloned().collect();
+        dirs.push(PathBuf::from(".aoc/templates"));
+        if let Some(project_dirs) = ProjectDirs::from("com.github", "gobanos", "cargo-aoc") {
+            dirs.push(project_dirs.config_dir().join("templates"));
+        }
+        dirs
+    }
+
+    /// Finds a user template, given by its path in the template directories
+    pub fn find_template(&self, name: &str) -> Option<PathBuf> {
+        self.template_dirs()
+            .into_iter()
+            .map(|dir| dir.join(name))
+            .find(|path| path.exists())
+    }
+
+    /// Loads a template from the user's templates, or the embedded one if none overrides it
+    pub fn template(&self, name: &str, builtin: &str) -> Result<String, Box<dyn error::Error>> {
+        match self.find_template(name) {
+            Some(path) => Ok(fs::read_to_string(path)?),
+            None => Ok(builtin.to_string()),
+        }
+    }
+
+    /// The arguments enabling features of the crate, for cargo commands of the generated crates
+    pub fn feature_args(&self, features: &[String]) -> Vec<String> {
+        if features.is_empty() {
+            return Vec::new();
+        }
+
+        let features = features
+            .iter()
+            .map(|feature| format!("{}/{}", self.name, feature))
+            .collect::<Vec<_>>()
+            .join(",");
+        vec!["--features".to_string(), features]
+    }
 
-        let status = process::Command::new("cargo").args(&args).spawn()?.wait()?;
+    /// Builds the crate running the solutions, and lists the solutions it was linked with
+    pub fn build_project(
+        &self,
+        runner_dir: &Path,
+        features: &[String],
+    ) -> Result<DayParts, Box<dyn error::Error>> {
+        let status = process::Command::new("cargo")
+            .args(["build", "--release", "--color=always"])
+            .args(self.feature_args(features))
+            .current_dir(runner_dir)
+            .spawn()?
+            .wait()?;
 
         if !status.success() {
             return Err(format!(
@@ -52,6 +121,89 @@ impl ProjectManager {
             .into());
         }
 
-        DayParts::load().map_err(|err| CouldNotLoadDayParts(err).into())
+        let output = process::Command::new("cargo")
+            .args(["run", "--release", "--quiet"])
+            .args(self.feature_args(features))
+            .args(["--", "--list"])
+            .current_dir(runner_dir)
+            .stderr(process::Stdio::inherit())
+            .output()?;
+
+        if !output.status.success() {
+            return Err(CouldNotLoadDayParts(
+                format!(
+                    "listing the solutions failed with code {}",
+                    output.status.code().unwrap_or(-1)
+                )
+                .into(),
+            )
+            .into());
+        }
+
+        parse_solutions(&String::from_utf8_lossy(&output.stdout))
+            .map_err(|err| CouldNotLoadDayParts(err).into())
+    }
+}
+
+/// Parses the solutions listed by the runner, like:
+/// ```text
+/// year 2018
+/// day1 part1
+/// day1 part2 Fnv
+/// day2 both
+/// ```
+/// Solutions of both parts are listed as `Part(0)`.
+fn parse_solutions(list: &str) -> Result<DayParts, Box<dyn error::Error>> {
+    let mut year = None;
+    let mut parts = Vec::new();
+
+    for line in list.lines() {
+        let mut words = line.split_whitespace();
+        match (words.next(), words.next(), words.next()) {
+            (Some("year"), Some(y), None) => year = Some(y.parse()?),
+            (Some(day), Some(part), name) => parts.push(DayPart {
+                day: day.parse()?,
+                part: match part {
+                    "both" => Part(0),
+                    part => part.parse()?,
+                },
+                name: name.map(String::from),
+            }),
+            _ => return Err(format!("unexpected line in the list of solutions: {line:?}").into()),
+        }
+    }
+
+    let mut day_parts = parts
+        .into_iter()
+        .collect::<DayPartsBuilder>()
+        .with_year(year.ok_or("the year is missing from the list of solutions")?);
+    day_parts.sort();
+
+    Ok(day_parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_runner_internal::Day;
+
+    #[test]
+    fn solutions_of_both_parts() {
+        let day_parts =
+            parse_solutions("year 2018\nday1 part1\nday1 both\nday1 both Fast\n").unwrap();
+        let parts: Vec<_> = day_parts
+            .iter()
+            .map(|dp| (dp.day, dp.part, dp.name.as_deref()))
+            .collect();
+
+        assert_eq!(day_parts.year, 2018);
+        assert_eq!(
+            parts,
+            [
+                (Day(1), Part(0), None),
+                (Day(1), Part(0), Some("Fast")),
+                (Day(1), Part(1), None),
+            ]
+        );
     }
 }
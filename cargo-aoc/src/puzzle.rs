@@ -0,0 +1,189 @@
+/// What can be read from a puzzle page
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    /// e.g. "Trebuchet?!" for `<h2>--- Day 1: Trebuchet?! ---</h2>`
+    pub title: Option<String>,
+    /// The parts unlocked so far
+    pub parts: Vec<PartExample>,
+}
+
+/// The example of a part, and its expected answer.
+/// Both are left out when they can't be told for sure.
+#[derive(Debug, Clone, Default)]
+pub struct PartExample {
+    pub input: Option<String>,
+    pub answer: Option<String>,
+}
+
+impl Puzzle {
+    pub fn parse(page: &str) -> Self {
+        let articles: Vec<&str> = page
+            .split(r#"<article class="day-desc">"#)
+            .skip(1)
+            .map(|article| article.split("</article>").next().unwrap_or(article))
+            .collect();
+
+        let title = articles.first().and_then(|article| {
+            let heading = between(article, "<h2>", "</h2>")?;
+            let heading = heading.trim().trim_matches('-').trim();
+            let title = heading.split_once(": ").map_or(heading, |(_, title)| title);
+            Some(unescape_html(title))
+        });
+
+        let mut parts: Vec<PartExample> = Vec::new();
+        for article in articles {
+            let blocks = code_blocks(article);
+            let input = match blocks.as_slice() {
+                [block] => Some(block.clone()),
+                // Part two usually goes on with the example of part one
+                [] => parts.last().and_then(|previous| previous.input.clone()),
+                _ => None,
+            };
+            parts.push(PartExample {
+                input,
+                answer: answer(article),
+            });
+        }
+
+        Puzzle { title, parts }
+    }
+
+    pub fn part(&self, part: usize) -> PartExample {
+        self.parts.get(part - 1).cloned().unwrap_or_default()
+    }
+}
+
+/// The content of every `<pre><code>` block, which is where examples are
+fn code_blocks(article: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut rest = article;
+    while let Some(start) = rest.find("<pre><code>") {
+        rest = &rest[start + "<pre><code>".len()..];
+        let Some(end) = rest.find("</code></pre>") else {
+            break;
+        };
+        let block = unescape_html(&strip_tags(&rest[..end]));
+        if !blocks.contains(&block) {
+            blocks.push(block);
+        }
+        rest = &rest[end..];
+    }
+    blocks
+}
+
+/// The answer of the example is the last value emphasized in the text, e.g. `<code><em>142</em></code>`
+fn answer(article: &str) -> Option<String> {
+    let (_, last) = article.rsplit_once("<code><em>")?;
+    let answer = last.split("</em></code>").next()?;
+    let answer = unescape_html(&strip_tags(answer));
+
+    if answer.is_empty() || answer.contains('\n') {
+        None
+    } else {
+        Some(answer)
+    }
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let from = text.find(start)? + start.len();
+    let to = from + text[from..].find(end)?;
+    Some(&text[from..to])
+}
+
+/// Removes the tags used for emphasis, e.g. in `<pre><code>a<em>b</em></code></pre>`
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Decodes the few HTML entities puzzle pages use
+fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = include_str!("../tests/fixtures/puzzle.html");
+
+    /// A page with an article per part
+    fn page(articles: &[&str]) -> String {
+        articles
+            .iter()
+            .map(|article| format!(r#"<article class="day-desc">{article}</article>"#))
+            .collect()
+    }
+
+    #[test]
+    fn title() {
+        assert_eq!(
+            Puzzle::parse(PAGE).title.as_deref(),
+            Some("Recursive Circus & Co")
+        );
+        assert_eq!(Puzzle::parse("<html></html>").title, None);
+    }
+
+    #[test]
+    fn examples() {
+        let puzzle = Puzzle::parse(PAGE);
+        let example = "pbga (66)\n\
+                       fwft (72) -> ktlj, cntj, xhth\n\
+                       tknk (41) -> ugml, padx, fwft\n";
+
+        assert_eq!(puzzle.parts.len(), 2);
+        assert_eq!(puzzle.part(1).input.as_deref(), Some(example));
+        assert_eq!(puzzle.part(1).answer.as_deref(), Some("tknk"));
+        // Part two reuses the example of part one
+        assert_eq!(puzzle.part(2).input.as_deref(), Some(example));
+        assert_eq!(puzzle.part(2).answer.as_deref(), Some("60"));
+    }
+
+    #[test]
+    fn several_examples() {
+        let puzzle = Puzzle::parse(&page(&[
+            "<pre><code>1\n2\n</code></pre><pre><code>3\n</code></pre>",
+            "<pre><code>4\n</code></pre><p>Again:</p><pre><code>4\n</code></pre>",
+        ]));
+
+        assert_eq!(puzzle.part(1).input, None);
+        // The same block twice is still a single example
+        assert_eq!(puzzle.part(2).input.as_deref(), Some("4\n"));
+        assert_eq!(puzzle.part(3).input, None);
+    }
+
+    #[test]
+    fn answers() {
+        let puzzle = Puzzle::parse(&page(&[
+            "<p><code><em>1</em></code> then <code><em>&lt;&amp;&gt;</em></code></p>",
+            "<p>No answer in <code>code</code> nor in <em>emphasis</em></p>",
+            "<p><code><em>\n</em></code></p>",
+        ]));
+
+        assert_eq!(puzzle.part(1).answer.as_deref(), Some("<&>"));
+        assert_eq!(puzzle.part(2).answer, None);
+        assert_eq!(puzzle.part(3).answer, None);
+    }
+
+    #[test]
+    fn entities() {
+        assert_eq!(
+            unescape_html("&lt;a href=&quot;x&quot;&gt;it&#39;s&lt;/a&gt; &amp;lt;"),
+            r#"<a href="x">it's</a> &lt;"#
+        );
+        assert_eq!(strip_tags("a<em>b</em>c"), "abc");
+    }
+}
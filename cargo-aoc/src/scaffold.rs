@@ -0,0 +1,138 @@
+use crate::codegen::{codegen, update_lib_rs};
+use crate::credentials::CredentialsManager;
+use crate::date::AOCDate;
+use crate::project::ProjectManager;
+use std::error;
+use std::fs;
+use std::path::Path;
+use toml_edit::{value, Document};
+
+/// The aoc-runner version this cargo-aoc works with, kept in sync with aoc-runner/Cargo.toml
+const AOC_RUNNER_VERSION: &str = "0.4";
+/// The aoc-runner-derive version this cargo-aoc works with, kept in sync with
+/// aoc-runner-derive/Cargo.toml
+const AOC_RUNNER_DERIVE_VERSION: &str = "0.4";
+
+/// Dependencies of an aoc-runner project, with their version
+const DEPENDENCIES: [(&str, &str); 2] = [
+    ("aoc-runner", AOC_RUNNER_VERSION),
+    ("aoc-runner-derive", AOC_RUNNER_DERIVE_VERSION),
+];
+
+/// Turns the crate in the current directory into an aoc-runner project.
+///
+/// `fresh` tells whether the crate was just created by cargo, in which case its `lib.rs` is
+/// replaced rather than completed. With `generate`, the puzzle page of day 1 is fetched with the
+/// session of `account` (the default one if `None`).
+pub fn scaffold(
+    year: u32,
+    fresh: bool,
+    generate: bool,
+    account: Option<&str>,
+) -> Result<(), Box<dyn error::Error>> {
+    add_dependencies()?;
+
+    let pm = ProjectManager::new()?;
+    let lib_rs_path = Path::new(pm.lib_path.as_deref().unwrap_or("src/lib.rs"));
+    let lib_rs = fs::read_to_string(lib_rs_path).unwrap_or_default();
+    let aoc_lib = format!("aoc_lib! {{ year = {year} }}\n");
+
+    if lib_rs.contains("aoc_lib!") {
+        eprintln!(
+            "{} already calls aoc_lib!. Skipping...",
+            lib_rs_path.display()
+        );
+    } else if fresh || lib_rs.trim().is_empty() {
+        if let Some(dir) = lib_rs_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(
+            lib_rs_path,
+            format!("use aoc_runner_derive::aoc_lib;\n\n{aoc_lib}"),
+        )?;
+    } else {
+        let separator = if lib_rs.ends_with('\n') { "\n" } else { "\n\n" };
+        fs::write(
+            lib_rs_path,
+            format!("{lib_rs}{separator}aoc_runner_derive::{aoc_lib}"),
+        )?;
+    }
+
+    ignore_inputs(&pm.config.input_dir)?;
+
+    if generate {
+        let date = AOCDate {
+            day: 1,
+            year: year as i32,
+        };
+        let session = CredentialsManager::new(account).get_session_token().ok();
+        codegen(date, &pm, session.as_deref())?;
+        update_lib_rs(date, &pm)?;
+        println!("Successfully generated boilerplate for day 1");
+    }
+    Ok(())
+}
+
+/// Adds the aoc-runner dependencies to Cargo.toml, keeping the ones already there
+fn add_dependencies() -> Result<(), Box<dyn error::Error>> {
+    let mut cargo: Document = fs::read_to_string("Cargo.toml")?.parse()?;
+    let dependencies = cargo
+        .entry("dependencies")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or("[dependencies] is not a table in Cargo.toml")?;
+
+    for (dependency, version) in DEPENDENCIES {
+        if !dependencies.contains_key(dependency) {
+            dependencies.insert(dependency, value(version));
+        }
+    }
+
+    fs::write("Cargo.toml", cargo.to_string())?;
+    Ok(())
+}
+
+/// Keeps the inputs out of git, as AoC asks people not to publish them
+fn ignore_inputs(input_dir: &Path) -> Result<(), Box<dyn error::Error>> {
+    let entry = format!("{}/", input_dir.display());
+    let gitignore = fs::read_to_string(".gitignore").unwrap_or_default();
+
+    let ignored = gitignore.lines().any(|line| {
+        let line = line.trim().trim_start_matches('/').trim_end_matches('/');
+        line == entry.trim_end_matches('/')
+    });
+    if !ignored {
+        let separator = if gitignore.is_empty() || gitignore.ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        fs::write(".gitignore", format!("{gitignore}{separator}{entry}\n"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The version of a crate of the workspace, as a Cargo.toml requirement like `0.3`
+    fn requirement(manifest: &str) -> String {
+        let manifest: toml::Table = manifest.parse().unwrap();
+        let version = manifest["package"]["version"].as_str().unwrap();
+        let mut version = version.split('.');
+        format!("{}.{}", version.next().unwrap(), version.next().unwrap())
+    }
+
+    #[test]
+    fn versions_in_sync() {
+        assert_eq!(
+            AOC_RUNNER_VERSION,
+            requirement(include_str!("../../aoc-runner/Cargo.toml"))
+        );
+        assert_eq!(
+            AOC_RUNNER_DERIVE_VERSION,
+            requirement(include_str!("../../aoc-runner-derive/Cargo.toml"))
+        );
+    }
+}
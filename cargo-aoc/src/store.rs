@@ -0,0 +1,102 @@
+use crate::date::AOCDate;
+use directories::ProjectDirs;
+use std::error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Account used when none is given nor set as default
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+/// Per-user storage for puzzle inputs.
+///
+/// AoC asks people not to publish their inputs: the store lives in the user's data directory,
+/// out of the repository, so inputs can't be committed by accident.
+///
+/// Inputs are kept per account so they never mix: `input/{year}` for the default account,
+/// `input/{account}/{year}` for the others.
+#[derive(Clone, Debug)]
+pub struct InputStore {
+    /// The project's input directory
+    local: PathBuf,
+    root: PathBuf,
+    /// Whether missing inputs are downloaded to the store rather than to the input directory
+    enabled: bool,
+}
+
+impl InputStore {
+    pub fn new(account: &str, input_dir: &Path, enabled: bool) -> Self {
+        let root = ProjectDirs::from("com.github", "gobanos", "cargo-aoc")
+            .map(|dirs| dirs.data_dir().join("inputs").join(account))
+            .expect("Home directory path could not be retrieved from the operating system");
+
+        let local = if account == DEFAULT_ACCOUNT {
+            input_dir.to_path_buf()
+        } else {
+            input_dir.join(account)
+        };
+
+        InputStore {
+            local,
+            root,
+            enabled,
+        }
+    }
+
+    /// Path of a given input inside the store
+    pub fn filename(&self, date: &AOCDate) -> PathBuf {
+        date.filename(&self.root)
+    }
+
+    /// Path of a given input inside the project's input directory
+    pub fn local_filename(&self, date: &AOCDate) -> PathBuf {
+        date.filename(&self.local)
+    }
+
+    /// Finds the input of a given day, looking into the input directory first, then into the store.
+    /// If neither has it, returns the path it should be downloaded to.
+    pub fn locate(&self, date: &AOCDate) -> PathBuf {
+        let local = self.local_filename(date);
+        let stored = self.filename(date);
+
+        if local.exists() {
+            local
+        } else if self.enabled || stored.exists() {
+            stored
+        } else {
+            local
+        }
+    }
+
+    /// Materializes a stored input into the input directory, either as a copy or as a symbolic
+    /// link. Returns `false` if there already was a file in the input directory.
+    pub fn export(&self, date: &AOCDate, link: bool) -> Result<bool, Box<dyn error::Error>> {
+        let stored = self.filename(date);
+        let local = self.local_filename(date);
+
+        if local.exists() {
+            return Ok(false);
+        }
+        if !stored.exists() {
+            return Err(format!("{} day {} is not in the input store", date.year, date.day).into());
+        }
+
+        fs::create_dir_all(date.directory(&self.local))?;
+        if link {
+            symlink(&stored, &local)?;
+        } else {
+            fs::copy(&stored, &local)?;
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(unix)]
+fn symlink(original: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(original, link)
+}
+
+#[cfg(windows)]
+fn symlink(original: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(original, link)
+}
@@ -7,7 +7,7 @@ authors = ["Grégory Obanos <gregory.obanos@gmail.com>"]
 {CRATE_NAME} = { path = "../../.." }
 
 # For release
-aoc-runner = "0.3"
+aoc-runner = "0.4"
 # For dev
 # aoc-runner = { path = "../../../../aoc-runner" }
 
@@ -7,6 +7,8 @@ use {CRATE_SLUG}::*;
 use aoc_runner::ArcStr;
 use criterion::Criterion;
 use std::fmt::Display;
+#[allow(unused_imports)]
+use std::time::Duration;
 
 #[inline]
 fn black_box(t: &dyn Display) {
@@ -15,6 +17,7 @@ fn black_box(t: &dyn Display) {
 
 fn aoc_benchmark(c: &mut Criterion) {
     {INPUTS}
+    {PARAMS}
 
     {PARTS}
 }
@@ -23,9 +26,14 @@ fn aoc_benchmark(c: &mut Criterion) {
 #[allow(dead_code)]
 fn input_benchmark(c: &mut Criterion) {
     {INPUTS}
+    {PARAMS}
 
     {GENS}
 }
 
-criterion_group!(benches, {BENCHMARKS});
+criterion_group! {
+    name = benches;
+    config = Criterion::default(){CONFIG};
+    targets = {BENCHMARKS}
+}
 criterion_main!(benches);
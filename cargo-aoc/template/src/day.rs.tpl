@@ -21,11 +21,11 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse("<EXAMPLE>")), "<RESULT>");
+        assert_eq!(part1(&parse({PART1_EXAMPLE})), {PART1_ANSWER});
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse("<EXAMPLE>")), "<RESULT>");
+        assert_eq!(part2(&parse({PART2_EXAMPLE})), {PART2_ANSWER});
     }
-}
\ No newline at end of file
+}
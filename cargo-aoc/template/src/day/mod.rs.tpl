@@ -0,0 +1,34 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+mod parse;
+
+#[aoc_generator({DAY})]
+fn generator(input: &str) -> String {
+    parse::parse(input)
+}
+
+#[aoc({DAY}, part1)]
+fn part1(input: &str) -> String {
+    todo!()
+}
+
+#[aoc({DAY}, part2)]
+fn part2(input: &str) -> String {
+    todo!()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&generator({PART1_EXAMPLE})), {PART1_ANSWER});
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&generator({PART2_EXAMPLE})), {PART2_ANSWER});
+    }
+}
@@ -2,13 +2,104 @@ extern crate {CRATE_SLUG};
 extern crate aoc_runner;
 
 use {CRATE_SLUG}::*;
+use aoc_runner::{ArcStr, Solution};
+use std::env;
+use std::fs;
 use std::time::Instant;
-use aoc_runner::ArcStr;
 
 fn main() {
-    println!("AOC {YEAR}");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let arg = |name: &str| {
+        args.iter()
+            .position(|arg| arg == name)
+            .and_then(|i| args.get(i + 1))
+            .map(String::as_str)
+    };
 
-    {INPUT}
+    // Tells cargo-aoc which solutions the crate has
+    if args.iter().any(|arg| arg == "--list") {
+        println!("year {}", YEAR);
+        for solution in Factory::solutions() {
+            let part = match solution.part {
+                0 => "both".to_string(),
+                part => format!("part{}", part),
+            };
+            match solution.name {
+                Some(name) => println!("day{} {} {}", solution.day, part, name),
+                None => println!("day{} {}", solution.day, part),
+            }
+        }
+        return;
+    }
 
-    {BODY}
-}
\ No newline at end of file
+    let day: u8 = arg("--day").and_then(|day| day.parse().ok()).expect("missing --day");
+    let part: Option<u8> = arg("--part").and_then(|part| part.parse().ok());
+    let label = arg("--label").unwrap_or_default();
+    let path = arg("--input").expect("missing --input");
+    let input = ArcStr::from(&fs::read_to_string(path).expect("failed to read the input"));
+
+    // Overrides the parameters of the solutions, given as `--param NAME=VALUE`
+    let overrides: Vec<(&str, &str)> = args.windows(2)
+        .filter(|pair| pair[0] == "--param")
+        .filter_map(|pair| pair[1].split_once('='))
+        .collect();
+    aoc_runner::Params::set_overrides(overrides.iter().cloned());
+
+    let solutions: Vec<&Solution> = Factory::solutions()
+        .into_iter()
+        .filter(|solution| solution.day == day && part.map_or(true, |part| solution.parts().contains(&part)))
+        .collect();
+
+    // Most likely a typo, as the solution runs with its default value
+    let mut unknown: Vec<&str> = overrides.iter()
+        .map(|&(name, _)| name)
+        .filter(|name| !solutions.iter().any(|solution| solution.params.contains(name)))
+        .collect();
+    unknown.sort();
+    unknown.dedup();
+    for name in unknown {
+        eprintln!("Warning: no solution of day {} declares the parameter `{}`, it is ignored", day, name);
+    }
+
+    println!("AOC {}", YEAR);
+
+    for solution in solutions {
+
+        let start_time = Instant::now();
+
+        match (solution.runner)(input.clone()) {
+            Ok(runner) => {
+                let inter_time = Instant::now();
+
+                // Solutions of both parts give both answers at once
+                let results = match solution.part {
+                    0 => runner.try_run_both().map(|(part1, part2)| vec![(1, part1), (2, part2)]),
+                    _ => runner.try_run().map(|result| vec![(solution.part, result)]),
+                };
+
+                match results {
+                    Ok(results) => {
+                        let final_time = Instant::now();
+                        let results: Vec<_> = results.into_iter().filter(|&(p, _)| part.map_or(true, |part| part == p)).collect();
+                        for (part, result) in &results {
+                            println!("{}{} : {:#}", Solution { part: *part, ..*solution }, label, result);
+                        }
+                        println!("\tgenerator: {:?},\n\trunner: {:?}", (inter_time - start_time), (final_time - inter_time));
+                        for (part, result) in &results {
+                            if let Some(expected) = arg(&format!("--expect{}", part)) {
+                                if result.to_string() == expected {
+                                    println!("\texpected: {} (correct)", expected);
+                                } else {
+                                    println!("\texpected: {} (WRONG)", expected);
+                                }
+                            }
+                        }
+                        println!();
+                    },
+                    Err(e) => eprintln!("{}{} : FAILED while running :\n{:#?}\n", solution, label, e)
+                }
+            },
+            Err(e) => eprintln!("{}{} : FAILED while generating :\n{:#?}\n", solution, label, e)
+        }
+    }
+}
@@ -0,0 +1,20 @@
+-- A Chromium Cookies database, version 18 of its schema
+CREATE TABLE meta (key LONGVARCHAR NOT NULL UNIQUE PRIMARY KEY, value LONGVARCHAR);
+INSERT INTO meta (key, value) VALUES ('mmap_status', '-1'), ('version', '18'), ('last_compatible_version', '18');
+CREATE TABLE cookies (
+    creation_utc INTEGER NOT NULL,
+    host_key TEXT NOT NULL,
+    top_frame_site_key TEXT NOT NULL DEFAULT '',
+    name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    encrypted_value BLOB NOT NULL DEFAULT '',
+    path TEXT NOT NULL,
+    expires_utc INTEGER NOT NULL,
+    is_secure INTEGER NOT NULL,
+    is_httponly INTEGER NOT NULL,
+    last_access_utc INTEGER NOT NULL
+);
+-- Stored unencrypted, like on systems without a keyring
+INSERT INTO cookies (creation_utc, host_key, name, value, encrypted_value, path, expires_utc, is_secure, is_httponly, last_access_utc) VALUES
+    (13340000000000000, '.adventofcode.com', 'session', 'plain-session', X'', '/', 13400000000000000, 1, 1, 13345000000000000),
+    (13340000000000000, '.example.com', 'session', 'other-site-session', X'', '/', 13400000000000000, 1, 1, 13346000000000000);
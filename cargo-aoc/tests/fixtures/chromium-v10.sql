@@ -0,0 +1,19 @@
+-- A Chromium Cookies database, version 21 of its schema
+CREATE TABLE meta (key LONGVARCHAR NOT NULL UNIQUE PRIMARY KEY, value LONGVARCHAR);
+INSERT INTO meta (key, value) VALUES ('mmap_status', '-1'), ('version', '21'), ('last_compatible_version', '21');
+CREATE TABLE cookies (
+    creation_utc INTEGER NOT NULL,
+    host_key TEXT NOT NULL,
+    top_frame_site_key TEXT NOT NULL DEFAULT '',
+    name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    encrypted_value BLOB NOT NULL DEFAULT '',
+    path TEXT NOT NULL,
+    expires_utc INTEGER NOT NULL,
+    is_secure INTEGER NOT NULL,
+    is_httponly INTEGER NOT NULL,
+    last_access_utc INTEGER NOT NULL
+);
+-- "chromium-session", encrypted with the "peanuts" password after a `v10` prefix
+INSERT INTO cookies (creation_utc, host_key, name, value, encrypted_value, path, expires_utc, is_secure, is_httponly, last_access_utc) VALUES
+    (13340000000000000, '.adventofcode.com', 'session', '', X'7631307f4d69985a78744c2ae2e5815fbbb3c3528e599e5bc6d4a5c5bce7cfa747f3ad', '/', 13400000000000000, 1, 1, 13345000000000000);
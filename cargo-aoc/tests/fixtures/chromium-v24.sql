@@ -0,0 +1,19 @@
+-- A Chromium Cookies database, version 24 of its schema
+CREATE TABLE meta (key LONGVARCHAR NOT NULL UNIQUE PRIMARY KEY, value LONGVARCHAR);
+INSERT INTO meta (key, value) VALUES ('mmap_status', '-1'), ('version', '24'), ('last_compatible_version', '24');
+CREATE TABLE cookies (
+    creation_utc INTEGER NOT NULL,
+    host_key TEXT NOT NULL,
+    top_frame_site_key TEXT NOT NULL DEFAULT '',
+    name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    encrypted_value BLOB NOT NULL DEFAULT '',
+    path TEXT NOT NULL,
+    expires_utc INTEGER NOT NULL,
+    is_secure INTEGER NOT NULL,
+    is_httponly INTEGER NOT NULL,
+    last_access_utc INTEGER NOT NULL
+);
+-- "prefixed-session" after the SHA-256 of ".adventofcode.com", encrypted like in version 21
+INSERT INTO cookies (creation_utc, host_key, name, value, encrypted_value, path, expires_utc, is_secure, is_httponly, last_access_utc) VALUES
+    (13340000000000000, '.adventofcode.com', 'session', '', X'763130a30958303ffead87bb6264e2df8238a399314c7104fb242a5066b5e7ae9c71a7a5f691191567a36b0576043abe3a660989adce44f084832c465a9648b91d0e09', '/', 13400000000000000, 1, 1, 13345000000000000);
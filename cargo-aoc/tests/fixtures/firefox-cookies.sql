@@ -0,0 +1,19 @@
+-- A Firefox cookies.sqlite, with two logins to AoC and another site's session
+CREATE TABLE moz_cookies (
+    id INTEGER PRIMARY KEY,
+    originAttributes TEXT NOT NULL DEFAULT '',
+    name TEXT,
+    value TEXT,
+    host TEXT,
+    path TEXT,
+    expiry INTEGER,
+    lastAccessed INTEGER,
+    creationTime INTEGER,
+    isSecure INTEGER,
+    isHttpOnly INTEGER
+);
+INSERT INTO moz_cookies (name, value, host, path, expiry, lastAccessed, creationTime, isSecure, isHttpOnly) VALUES
+    ('session', 'old-firefox-session', '.adventofcode.com', '/', 2000000000, 1700000000000000, 1690000000000000, 1, 1),
+    ('session', 'firefox-session', '.adventofcode.com', '/', 2000000000, 1701000000000000, 1700500000000000, 1, 1),
+    ('session', 'other-site-session', '.example.com', '/', 2000000000, 1702000000000000, 1700000000000000, 1, 1),
+    ('_ga', 'GA1.2.3.4', '.adventofcode.com', '/', 2000000000, 1703000000000000, 1690000000000000, 0, 0);